@@ -0,0 +1,109 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Embed the pristine exercise stubs into the binary so that
+// `algo reset` can restore them without relying on git.
+// The stubs are taken from the last commit rather than from the working
+// tree, which learners edit, so rebuilding after solving an exercise
+// does not turn the solution into the stub.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let stubs_dir = out_dir.join("pristine");
+    let _ = fs::remove_dir_all(&stubs_dir);
+
+    let mut files = match committed_exercises(&manifest_dir, &stubs_dir) {
+        Some(files) => files,
+        None => {
+            // Without a git checkout, such as in a source archive, the
+            // working tree is the only copy of the stubs there is
+            println!(
+                "cargo:warning=Could not read the exercises from git, embedding the working tree"
+            );
+            let mut files = Vec::new();
+            collect_exercises(Path::new("fundamentals"), &mut files).unwrap();
+            files
+                .into_iter()
+                .map(|file| {
+                    let source = manifest_dir.join(&file);
+                    (file, source)
+                })
+                .collect()
+        }
+    };
+    files.sort();
+
+    let mut generated = String::from("pub static PRISTINE_EXERCISES: &[(&str, &str)] = &[\n");
+    for (file, source) in files {
+        let key = file.to_string_lossy().replace('\\', "/");
+        generated.push_str(&format!("    ({:?}, include_str!({:?})),\n", key, source));
+    }
+    generated.push_str("];\n");
+
+    fs::write(out_dir.join("pristine.rs"), generated).unwrap();
+}
+
+// Write every exercise of the last commit under the given directory,
+// returning the path of each exercise along with the copy written
+fn committed_exercises(manifest_dir: &Path, stubs_dir: &Path) -> Option<Vec<(PathBuf, PathBuf)>> {
+    let listing = git(
+        manifest_dir,
+        &["ls-tree", "-r", "--name-only", "HEAD", "--", "fundamentals"],
+    )?;
+
+    // Embed the stubs again once something is committed
+    for reference in &["HEAD", "packed-refs"] {
+        rerun_if_git_path_changed(manifest_dir, reference);
+    }
+    if let Some(branch) = git(manifest_dir, &["symbolic-ref", "-q", "HEAD"]) {
+        rerun_if_git_path_changed(manifest_dir, branch.trim());
+    }
+
+    let mut files = Vec::new();
+    for file in listing.lines().filter(|file| file.ends_with(".rs")) {
+        let source = git(manifest_dir, &["show", &format!("HEAD:{}", file)])?;
+        let copy = stubs_dir.join(file);
+        fs::create_dir_all(copy.parent()?).ok()?;
+        fs::write(&copy, source).ok()?;
+        files.push((PathBuf::from(file), copy));
+    }
+    Some(files)
+}
+
+fn rerun_if_git_path_changed(manifest_dir: &Path, name: &str) {
+    if let Some(path) = git(manifest_dir, &["rev-parse", "--git-path", name]) {
+        println!(
+            "cargo:rerun-if-changed={}",
+            manifest_dir.join(path.trim()).display()
+        );
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+fn collect_exercises(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_exercises(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
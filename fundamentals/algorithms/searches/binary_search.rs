@@ -6,6 +6,8 @@
  * Repeatedly check until the value is found or the interval is empty.
  */
 
+// I AM NOT DONE
+
 fn binary_search<T: PartialEq + PartialOrd>(item: &T, array: &[T]) -> i32 {
   // TODO
 }
@@ -13,10 +15,11 @@ fn binary_search<T: PartialEq + PartialOrd>(item: &T, array: &[T]) -> i32 {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use support::samples::SORTED_STRINGS;
 
   #[test]
   fn test_binary_search() {
-    let mut strings = vec!["beach", "hotel", "airplane", "car", "house", "art"];
+    let strings = SORTED_STRINGS;
     let index = binary_search(&"car", &strings);
     assert_eq!(index, 3);
   }
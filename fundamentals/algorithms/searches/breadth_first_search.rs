@@ -4,6 +4,8 @@
  * It uses the opposite strategy of depth-first search, which instead explores the node branch as far as possible before being forced to backtrack and expand other nodes.
  */
 
+// I AM NOT DONE
+
 fn bfs<T: PartialEq + PartialOrd>(item: &T, array: &[T]) -> i32 {
   // TODO
 }
@@ -11,10 +13,11 @@ fn bfs<T: PartialEq + PartialOrd>(item: &T, array: &[T]) -> i32 {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use support::samples::STRINGS;
 
   #[test]
   fn test_bfs() {
-    let mut strings = vec!["beach", "hotel", "airplane", "car", "house", "art"];
+    let strings = STRINGS;
     let index = bfs(&"car", &strings);
     assert_eq!(index, 3);
   }
@@ -3,6 +3,8 @@
  * The algorithm starts at the root node (selecting some arbitrary node as the root node in the case of a graph) and explores as far as possible along each branch before backtracking.
  */
 
+// I AM NOT DONE
+
 fn dfs<T: PartialEq + PartialOrd>(item: &T, array: &[T]) -> i32 {
   // TODO
 }
@@ -10,10 +12,11 @@ fn dfs<T: PartialEq + PartialOrd>(item: &T, array: &[T]) -> i32 {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use support::samples::STRINGS;
 
   #[test]
   fn test_dfs() {
-    let mut strings = vec!["beach", "hotel", "airplane", "car", "house", "art"];
+    let strings = STRINGS;
     let index = dfs(&"car", &strings);
     assert_eq!(index, 3);
   }
@@ -6,18 +6,21 @@
  * The idea is to use bucket sort.
  */
 
-fn bucket_sort<T: Ord>(array: &mut [T]) {
+// I AM NOT DONE
+
+fn bucket_sort(array: &mut [f64]) {
   // TODO
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use support::samples::{FRACTIONS, SORTED_FRACTIONS};
 
   #[test]
   fn test_ascending_number_sorting() {
-    let mut numbers = [0.897, 0.565, 0.656, 0.1234, 0.665, 0.3434];
-    bubble_sort(&mut numbers);
-    assert_eq!(numbers, [0.1234, 0.3434, 0.565, 0.656, 0.665, 0.897]);
+    let mut numbers = FRACTIONS;
+    bucket_sort(&mut numbers);
+    assert_eq!(numbers, SORTED_FRACTIONS);
   }
 }
@@ -1,9 +1,12 @@
 /*
- * Counting sort is a sorting technique based on keys between a specific range.
- * It works by counting the number of objects having distinct key values (kind of hashing).
- * Then doing some arithmetic to calculate the position of each object in the output sequence.
+ * Comparison counting sort is a sorting technique which counts, for each element,
+ * how many elements of the array are smaller than it, or equal to it and placed before it.
+ * That count is the position of the element in the output sequence.
+ * Comparing every pair of elements makes it quadratic, but works for any ordered type.
  */
 
+// I AM NOT DONE
+
 fn counting_sort<T: Ord>(array: &mut [T]) {
   // TODO
 }
@@ -11,18 +14,19 @@ fn counting_sort<T: Ord>(array: &mut [T]) {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use support::samples::{NUMBERS, SORTED_NUMBERS, STRINGS, SORTED_STRINGS};
 
   #[test]
   fn test_ascending_number_sorting() {
-    let mut numbers = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+    let mut numbers = NUMBERS;
     counting_sort(&mut numbers);
-    assert_eq!(numbers, [-31, 0, 1, 2, 2, 4, 65, 83, 99, 782]);
+    assert_eq!(numbers, SORTED_NUMBERS);
   }
 
   #[test]
   fn test_alphabetical_strings_sorting() {
-    let mut strings = ["beach", "hotel", "airplane", "car", "house", "art"];
+    let mut strings = STRINGS;
     counting_sort(&mut strings);
-    assert_eq!(strings, ["airplane", "art", "beach", "car", "hotel", "house"]);
+    assert_eq!(strings, SORTED_STRINGS);
   }
 }
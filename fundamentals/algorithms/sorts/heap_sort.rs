@@ -4,6 +4,8 @@
  * We repeat the same process for the remaining elements.
  */
 
+// I AM NOT DONE
+
 fn heap_sort<T: Ord>(array: &mut [T]) {
   // TODO
 }
@@ -11,18 +13,19 @@ fn heap_sort<T: Ord>(array: &mut [T]) {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use support::samples::{NUMBERS, SORTED_NUMBERS, STRINGS, SORTED_STRINGS};
 
   #[test]
   fn test_ascending_number_sorting() {
-    let mut numbers = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+    let mut numbers = NUMBERS;
     heap_sort(&mut numbers);
-    assert_eq!(numbers, [-31, 0, 1, 2, 2, 4, 65, 83, 99, 782]);
+    assert_eq!(numbers, SORTED_NUMBERS);
   }
 
   #[test]
   fn test_alphabetical_strings_sorting() {
-    let mut strings = ["beach", "hotel", "airplane", "car", "house", "art"];
+    let mut strings = STRINGS;
     heap_sort(&mut strings);
-    assert_eq!(strings, ["airplane", "art", "beach", "car", "hotel", "house"]);
+    assert_eq!(strings, SORTED_STRINGS);
   }
 }
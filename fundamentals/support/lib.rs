@@ -0,0 +1,7 @@
+/*
+ * Helpers shared by every exercise.
+ * This crate is built once by `algo` and available to the exercises as `support`,
+ * for example `use support::samples::NUMBERS;`.
+ */
+
+pub mod samples;
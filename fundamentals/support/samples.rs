@@ -0,0 +1,13 @@
+/*
+ * The sample arrays the tests of the exercises sort and search,
+ * along with their sorted versions.
+ */
+
+pub const NUMBERS: [i32; 10] = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+pub const SORTED_NUMBERS: [i32; 10] = [-31, 0, 1, 2, 2, 4, 65, 83, 99, 782];
+
+pub const STRINGS: [&str; 6] = ["beach", "hotel", "airplane", "car", "house", "art"];
+pub const SORTED_STRINGS: [&str; 6] = ["airplane", "art", "beach", "car", "hotel", "house"];
+
+pub const FRACTIONS: [f64; 6] = [0.897, 0.565, 0.656, 0.1234, 0.665, 0.3434];
+pub const SORTED_FRACTIONS: [f64; 6] = [0.1234, 0.3434, 0.565, 0.656, 0.665, 0.897];
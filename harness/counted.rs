@@ -0,0 +1,40 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+thread_local! {
+    static COMPARISONS: Cell<u64> = Cell::new(0);
+}
+
+// A value which counts every comparison made with it on the current thread
+#[derive(Copy, Clone, Debug)]
+pub struct Counted<T>(pub T);
+
+impl<T: Ord> Ord for Counted<T> {
+    fn cmp(&self, other: &Counted<T>) -> Ordering {
+        COMPARISONS.with(|count| count.set(count.get() + 1));
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: Ord> PartialOrd for Counted<T> {
+    fn partial_cmp(&self, other: &Counted<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> PartialEq for Counted<T> {
+    fn eq(&self, other: &Counted<T>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Ord> Eq for Counted<T> {}
+
+// The number of comparisons made on the current thread since the last reset
+pub fn comparisons() -> u64 {
+    COMPARISONS.with(Cell::get)
+}
+
+pub fn reset_comparisons() {
+    COMPARISONS.with(|count| count.set(0));
+}
@@ -0,0 +1,31 @@
+// A splitmix64 generator, which gives the same numbers for the same seed
+struct Rng(u64);
+
+#[allow(dead_code)]
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A number between 0 and n, excluded
+    fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "There is no number below 0");
+        (self.next() % n as u64) as usize
+    }
+}
+
+// The n pseudo-random numbers below one million the exercises are measured with,
+// the same ones at every run. They are in ascending order if sorted is set
+#[allow(dead_code)]
+fn input(n: usize, sorted: bool) -> Vec<i64> {
+    let mut rng = Rng(n as u64);
+    let mut input: Vec<i64> = (0..n).map(|_| rng.below(1_000_000) as i64).collect();
+    if sorted {
+        input.sort();
+    }
+    input
+}
@@ -0,0 +1,137 @@
+use std::panic;
+
+// The `Rng` of `harness/input.rs` also gives the small numbers of the cases
+impl Rng {
+    fn small(&mut self) -> i64 {
+        self.below(201) as i64 - 100
+    }
+}
+
+// The input of the given case. The cases take every shape in turn:
+// empty, a single element, many duplicates, sorted, reversed,
+// extreme values and small random numbers
+fn generate(rng: &mut Rng, case: usize) -> Vec<i64> {
+    let len = rng.below(48);
+    match case % 7 {
+        0 => Vec::new(),
+        1 => vec![rng.next() as i64],
+        2 => (0..len).map(|_| rng.below(4) as i64).collect(),
+        3 => {
+            let mut input: Vec<i64> = (0..len).map(|_| rng.small()).collect();
+            input.sort();
+            input
+        }
+        4 => {
+            let mut input: Vec<i64> = (0..len).map(|_| rng.small()).collect();
+            input.sort_by(|a, b| b.cmp(a));
+            input
+        }
+        5 => {
+            let extremes = [i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX - 1, i64::MAX];
+            (0..len)
+                .map(|_| extremes[rng.below(extremes.len())])
+                .collect()
+        }
+        _ => (0..len).map(|_| rng.small()).collect(),
+    }
+}
+
+// Check the given number of generated cases against the reference check,
+// and fail with the first failing case, shrunk to a minimal counterexample.
+// The item of a search is either one of the elements or a random number,
+// and the inputs of a search are sorted if sorted is set
+pub fn check_property(
+    seed: u64,
+    cases: usize,
+    search: bool,
+    sorted: bool,
+    check: fn(&[i64], i64) -> Result<(), String>,
+) {
+    // The panics of the exercise are part of the counterexample, not of the output
+    panic::set_hook(Box::new(|_| {}));
+    let mut rng = Rng(seed);
+    for case in 0..cases {
+        let mut input = generate(&mut rng, case);
+        if sorted {
+            input.sort();
+        }
+        let item = if !input.is_empty() && rng.below(2) == 0 {
+            input[rng.below(input.len())]
+        } else {
+            rng.small()
+        };
+        if check(&input, item).is_ok() {
+            continue;
+        }
+
+        let (input, item) = shrink(input, item, sorted, check);
+        let error = check(&input, item).unwrap_err();
+        let _ = panic::take_hook();
+        let mut message = format!(
+            "Counterexample found after {} cases generated with the seed {}:\n",
+            case + 1,
+            seed
+        );
+        message.push_str(&format!("  input: {:?}\n", input));
+        if search {
+            message.push_str(&format!("  item: {}\n", item));
+        }
+        message.push_str(&format!("  {}", error));
+        panic!("{}", message);
+    }
+}
+
+// Shrink a failing case until none of its smaller versions fails anymore
+fn shrink(
+    mut input: Vec<i64>,
+    mut item: i64,
+    sorted: bool,
+    check: fn(&[i64], i64) -> Result<(), String>,
+) -> (Vec<i64>, i64) {
+    loop {
+        let smaller = candidates(&input, item, sorted)
+            .into_iter()
+            .find(|(input, item)| check(input, *item).is_err());
+        match smaller {
+            Some(smaller) => (input, item) = smaller,
+            None => return (input, item),
+        }
+    }
+}
+
+// The smaller versions of a case: either half of the input, the input without
+// one of its elements, with one element closer to zero, or the item closer to zero
+fn candidates(input: &[i64], item: i64, sorted: bool) -> Vec<(Vec<i64>, i64)> {
+    let mut candidates = Vec::new();
+    let len = input.len();
+    if len > 1 {
+        candidates.push((input[..len / 2].to_vec(), item));
+        candidates.push((input[len / 2..].to_vec(), item));
+    }
+    for i in 0..len {
+        let mut smaller = input.to_vec();
+        smaller.remove(i);
+        candidates.push((smaller, item));
+    }
+    for i in 0..len {
+        for value in closer_to_zero(input[i]) {
+            let mut smaller = input.to_vec();
+            smaller[i] = value;
+            if sorted {
+                smaller.sort();
+            }
+            candidates.push((smaller, item));
+        }
+    }
+    for value in closer_to_zero(item) {
+        candidates.push((input.to_vec(), value));
+    }
+    candidates
+}
+
+fn closer_to_zero(value: i64) -> Vec<i64> {
+    let mut values = vec![0, value / 2, value - value.signum()];
+    values.dedup();
+    values.retain(|&smaller| smaller != value);
+    values
+}
@@ -0,0 +1,37 @@
+use std::cmp::Ordering;
+
+// A record ordered by its key only, remembering its index before sorting
+#[derive(Copy, Clone, Debug)]
+pub struct Keyed {
+    pub key: i64,
+    pub index: usize,
+}
+
+impl Ord for Keyed {
+    fn cmp(&self, other: &Keyed) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Keyed) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Keyed {
+    fn eq(&self, other: &Keyed) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Keyed {}
+
+// The first two neighbouring records with equal keys which are no longer
+// in their original order, if the sort wasn't stable
+pub fn first_unstable(records: &[Keyed]) -> Option<(Keyed, Keyed)> {
+    records
+        .windows(2)
+        .find(|pair| pair[0].key == pair[1].key && pair[0].index > pair[1].index)
+        .map(|pair| (pair[0], pair[1]))
+}
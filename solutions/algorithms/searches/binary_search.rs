@@ -0,0 +1,35 @@
+/*
+ * Search a sorted array by repeatedly dividing the search interval in half.
+ * Begin with an interval covering the whole array.
+ * If the value of the search key is less than the item in the middle of the interval, narrow the interval to the lower half.
+ * Otherwise narrow it to the upper half.
+ * Repeatedly check until the value is found or the interval is empty.
+ */
+
+fn binary_search<T: PartialEq + PartialOrd>(item: &T, array: &[T]) -> i32 {
+  let (mut low, mut high) = (0, array.len());
+  while low < high {
+    let middle = low + (high - low) / 2;
+    if array[middle] == *item {
+      return middle as i32;
+    } else if *item < array[middle] {
+      high = middle;
+    } else {
+      low = middle + 1;
+    }
+  }
+  -1
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use support::samples::SORTED_STRINGS;
+
+  #[test]
+  fn test_binary_search() {
+    let strings = SORTED_STRINGS;
+    let index = binary_search(&"car", &strings);
+    assert_eq!(index, 3);
+  }
+}
@@ -0,0 +1,37 @@
+/*
+ * Breadth-first search (BFS) is an algorithm for traversing or searching tree or graph data structures.
+ * It starts at the tree root (or some arbitrary node of a graph, sometimes referred to as a 'search key'[1]), and explores all of the neighbor nodes at the present depth prior to moving on to the nodes at the next depth level.
+ * It uses the opposite strategy of depth-first search, which instead explores the node branch as far as possible before being forced to backtrack and expand other nodes.
+ */
+
+use std::collections::VecDeque;
+
+// The array is an implicit binary tree: the children of index i are 2i + 1 and 2i + 2
+fn bfs<T: PartialEq + PartialOrd>(item: &T, array: &[T]) -> i32 {
+  let mut queue = VecDeque::new();
+  queue.push_back(0);
+  while let Some(node) = queue.pop_front() {
+    if node >= array.len() {
+      continue;
+    }
+    if array[node] == *item {
+      return node as i32;
+    }
+    queue.push_back(2 * node + 1);
+    queue.push_back(2 * node + 2);
+  }
+  -1
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use support::samples::STRINGS;
+
+  #[test]
+  fn test_bfs() {
+    let strings = STRINGS;
+    let index = bfs(&"car", &strings);
+    assert_eq!(index, 3);
+  }
+}
@@ -0,0 +1,34 @@
+/*
+ * Depth-first search (DFS) is an algorithm for traversing or searching tree or graph data structures.
+ * The algorithm starts at the root node (selecting some arbitrary node as the root node in the case of a graph) and explores as far as possible along each branch before backtracking.
+ */
+
+// The array is an implicit binary tree: the children of index i are 2i + 1 and 2i + 2
+fn dfs<T: PartialEq + PartialOrd>(item: &T, array: &[T]) -> i32 {
+  let mut stack = vec![0];
+  while let Some(node) = stack.pop() {
+    if node >= array.len() {
+      continue;
+    }
+    if array[node] == *item {
+      return node as i32;
+    }
+    // Push the right child first so the left branch is explored first
+    stack.push(2 * node + 2);
+    stack.push(2 * node + 1);
+  }
+  -1
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use support::samples::STRINGS;
+
+  #[test]
+  fn test_dfs() {
+    let strings = STRINGS;
+    let index = dfs(&"car", &strings);
+    assert_eq!(index, 3);
+  }
+}
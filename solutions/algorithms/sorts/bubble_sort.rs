@@ -0,0 +1,43 @@
+/*
+ * Bubble sorting is the simplest sorting algorithm.
+ * It simply iterates over the entire list and compare adjacent elements in the list, and after each comparison, place them in the right order of magnitude.
+ * This works by swapping adjacent items if they are not in the correct order.
+ * The process is repeated n-1 times for a list of n items.
+ * In each such iteration, the largest element is arranged in the end.
+ */
+
+fn bubble_sort<T: Ord>(array: &mut [T]) {
+  let n = array.len();
+  for pass in 1..n {
+    let mut swapped = false;
+    for i in 0..n - pass {
+      if array[i] > array[i + 1] {
+        array.swap(i, i + 1);
+        swapped = true;
+      }
+    }
+    if !swapped {
+      return;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use support::samples::{NUMBERS, SORTED_NUMBERS, STRINGS, SORTED_STRINGS};
+
+  #[test]
+  fn test_ascending_number_sorting() {
+    let mut numbers = NUMBERS;
+    bubble_sort(&mut numbers);
+    assert_eq!(numbers, SORTED_NUMBERS);
+  }
+
+  #[test]
+  fn test_alphabetical_strings_sorting() {
+    let mut strings = STRINGS;
+    bubble_sort(&mut strings);
+    assert_eq!(strings, SORTED_STRINGS);
+  }
+}
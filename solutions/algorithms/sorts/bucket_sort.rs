@@ -0,0 +1,50 @@
+/*
+ * Bucket sort is mainly useful when input is uniformly distributed over a range. For example, consider the following problem. 
+ * Sort a large set of floating point numbers which are in range from 0.0 to 1.0 and are uniformly distributed across the range. How do we sort the numbers efficiently?
+ * A simple way is to apply a comparison based sorting algorithm. The lower bound for Comparison based sorting algorithm (Merge Sort, Heap Sort, Quick-Sort .. etc) is Ω(n Log n), i.e., they cannot do better than nLogn.
+ * Can we sort the array in linear time? Counting sort can not be applied here as we use keys as index in counting sort. Here keys are floating point numbers.
+ * The idea is to use bucket sort.
+ */
+
+fn bucket_sort(array: &mut [f64]) {
+  let n = array.len();
+  if n <= 1 {
+    return;
+  }
+
+  // Values are expected in [0.0, 1.0), so value * n picks one of n buckets
+  let mut buckets: Vec<Vec<f64>> = vec![Vec::new(); n];
+  for &value in array.iter() {
+    let index = ((value * n as f64) as usize).min(n - 1);
+    buckets[index].push(value);
+  }
+
+  let mut i = 0;
+  for mut bucket in buckets {
+    // Buckets are small, insertion sort is enough
+    for j in 1..bucket.len() {
+      let mut k = j;
+      while k > 0 && bucket[k - 1] > bucket[k] {
+        bucket.swap(k - 1, k);
+        k -= 1;
+      }
+    }
+    for value in bucket {
+      array[i] = value;
+      i += 1;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use support::samples::{FRACTIONS, SORTED_FRACTIONS};
+
+  #[test]
+  fn test_ascending_number_sorting() {
+    let mut numbers = FRACTIONS;
+    bucket_sort(&mut numbers);
+    assert_eq!(numbers, SORTED_FRACTIONS);
+  }
+}
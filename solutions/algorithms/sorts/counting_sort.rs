@@ -0,0 +1,50 @@
+/*
+ * Comparison counting sort is a sorting technique which counts, for each element,
+ * how many elements of the array are smaller than it, or equal to it and placed before it.
+ * That count is the position of the element in the output sequence.
+ * Comparing every pair of elements makes it quadratic, but works for any ordered type.
+ */
+
+fn counting_sort<T: Ord>(array: &mut [T]) {
+  let n = array.len();
+
+  // Comparison counting: the final position of an element is the number of
+  // elements smaller than it, plus the number of equal elements before it.
+  let mut positions = vec![0; n];
+  for i in 0..n {
+    for j in 0..n {
+      if array[j] < array[i] || (array[j] == array[i] && j < i) {
+        positions[i] += 1;
+      }
+    }
+  }
+
+  // Apply the permutation in place by following its cycles
+  for i in 0..n {
+    while positions[i] != i {
+      let target = positions[i];
+      array.swap(i, target);
+      positions.swap(i, target);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use support::samples::{NUMBERS, SORTED_NUMBERS, STRINGS, SORTED_STRINGS};
+
+  #[test]
+  fn test_ascending_number_sorting() {
+    let mut numbers = NUMBERS;
+    counting_sort(&mut numbers);
+    assert_eq!(numbers, SORTED_NUMBERS);
+  }
+
+  #[test]
+  fn test_alphabetical_strings_sorting() {
+    let mut strings = STRINGS;
+    counting_sort(&mut strings);
+    assert_eq!(strings, SORTED_STRINGS);
+  }
+}
@@ -0,0 +1,55 @@
+/*
+ * Heap sort is a comparison based sorting technique based on Binary Heap data structure.
+ * It is similar to selection sort where we first find the maximum element and place the maximum element at the end.
+ * We repeat the same process for the remaining elements.
+ */
+
+fn heap_sort<T: Ord>(array: &mut [T]) {
+  let n = array.len();
+  for root in (0..n / 2).rev() {
+    sift_down(array, root, n);
+  }
+
+  for end in (1..n).rev() {
+    array.swap(0, end);
+    sift_down(array, 0, end);
+  }
+}
+
+// Move the element at root down until array[..end] is a max heap again
+fn sift_down<T: Ord>(array: &mut [T], mut root: usize, end: usize) {
+  loop {
+    let mut child = 2 * root + 1;
+    if child >= end {
+      return;
+    }
+    if child + 1 < end && array[child] < array[child + 1] {
+      child += 1;
+    }
+    if array[root] >= array[child] {
+      return;
+    }
+    array.swap(root, child);
+    root = child;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use support::samples::{NUMBERS, SORTED_NUMBERS, STRINGS, SORTED_STRINGS};
+
+  #[test]
+  fn test_ascending_number_sorting() {
+    let mut numbers = NUMBERS;
+    heap_sort(&mut numbers);
+    assert_eq!(numbers, SORTED_NUMBERS);
+  }
+
+  #[test]
+  fn test_alphabetical_strings_sorting() {
+    let mut strings = STRINGS;
+    heap_sort(&mut strings);
+    assert_eq!(strings, SORTED_STRINGS);
+  }
+}
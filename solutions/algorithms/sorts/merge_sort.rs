@@ -0,0 +1,55 @@
+/*
+ * Merge Sort is a Divide and Conquer algorithm.
+ * It divides the input array into two halves, calls itself for the two halves, and then merges the two sorted halves.
+ * The merge() function is used for merging two halves.
+ * The merge(arr, l, m, r) is a key process that assumes that arr[l..m] and arr[m+1..r] are sorted and merges the two sorted sub-arrays into one.
+ * See the following Rust implementation for details.
+ */
+
+fn merge_sort<T: Copy + Ord>(x: &mut [T]) {
+  let n = x.len();
+  if n <= 1 {
+    return;
+  }
+
+  let middle = n / 2;
+  merge_sort(&mut x[..middle]);
+  merge_sort(&mut x[middle..]);
+
+  let mut merged = Vec::with_capacity(n);
+  let (mut left, mut right) = (0, middle);
+  while left < middle && right < n {
+    // Taking from the left half on ties keeps the sort stable
+    if x[left] <= x[right] {
+      merged.push(x[left]);
+      left += 1;
+    } else {
+      merged.push(x[right]);
+      right += 1;
+    }
+  }
+  merged.extend_from_slice(&x[left..middle]);
+  merged.extend_from_slice(&x[right..]);
+
+  x.copy_from_slice(&merged);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use support::samples::{NUMBERS, SORTED_NUMBERS, STRINGS, SORTED_STRINGS};
+
+  #[test]
+  fn test_ascending_number_sorting() {
+    let mut numbers = NUMBERS;
+    merge_sort(&mut numbers);
+    assert_eq!(numbers, SORTED_NUMBERS);
+  }
+
+  #[test]
+  fn test_alphabetical_strings_sorting() {
+    let mut strings = STRINGS;
+    merge_sort(&mut strings);
+    assert_eq!(strings, SORTED_STRINGS);
+  }
+}
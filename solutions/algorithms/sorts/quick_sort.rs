@@ -0,0 +1,53 @@
+/*
+ * QuickSort is a Divide and Conquer algorithm. It picks an element as pivot and partitions the given array around the picked pivot. There are many different versions of quickSort that pick pivot in different ways. 
+ * - Always pick first element as pivot.
+ * - Always pick last element as pivot (implemented below)
+ * - Pick a random element as pivot.
+ * - Pick median as pivot.
+ */
+
+fn quick_sort<T: Ord>(array: &mut [T]) {
+  if array.len() <= 1 {
+    return;
+  }
+
+  let pivot = partition(array);
+  let (lower, upper) = array.split_at_mut(pivot);
+  quick_sort(lower);
+  quick_sort(&mut upper[1..]);
+}
+
+// Lomuto partition scheme: the last element is the pivot.
+// Returns the final index of the pivot.
+fn partition<T: Ord>(array: &mut [T]) -> usize {
+  let last = array.len() - 1;
+  let mut store = 0;
+  for i in 0..last {
+    if array[i] <= array[last] {
+      array.swap(i, store);
+      store += 1;
+    }
+  }
+  array.swap(store, last);
+  store
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use support::samples::{NUMBERS, SORTED_NUMBERS, STRINGS, SORTED_STRINGS};
+
+  #[test]
+  fn test_ascending_number_sorting() {
+    let mut numbers = NUMBERS;
+    quick_sort(&mut numbers);
+    assert_eq!(numbers, SORTED_NUMBERS);
+  }
+
+  #[test]
+  fn test_alphabetical_strings_sorting() {
+    let mut strings = STRINGS;
+    quick_sort(&mut strings);
+    assert_eq!(strings, SORTED_STRINGS);
+  }
+}
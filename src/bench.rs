@@ -0,0 +1,403 @@
+use crate::complexity::format_nanos;
+use crate::diagnostic::print_diagnostics;
+use crate::exercise::{Exercise, ExerciseOutput};
+use crate::harness::{panic_message, INPUT_SOURCE};
+use crate::progress::{now, Progress};
+use crate::property::Property;
+use console::style;
+use indicatif::ProgressBar;
+use serde::{Deserialize, Serialize};
+
+// The name of the test which benchmarks an exercise
+pub const HARNESS_TEST: &str = "__algo_bench::bench";
+// The prefix of the lines the harness prints, one per timing
+const TIMING_PREFIX: &str = "algo-bench";
+// The sizes of the inputs, the larger ones are skipped once a run takes too long
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+// The name the timings of the exercise itself are recorded under
+const YOURS: &str = "yours";
+
+// The implementations of the standard library an exercise is compared with,
+// and the expression running them on `input`
+fn baselines(property: Option<Property>) -> &'static [(&'static str, &'static str)] {
+    match property {
+        Some(Property::Sort) => &[
+            ("sort_unstable", "input.sort_unstable()"),
+            ("sort", "input.sort()"),
+        ],
+        Some(Property::SortedSearch) => &[("binary_search", "input.binary_search(&item)")],
+        Some(Property::Search) => &[("position", "input.iter().position(|x| *x == item)")],
+        None => &[],
+    }
+}
+
+// The kinds of input an exercise is timed on, and the expression preparing
+// the `Vec<i64>` of n numbers along with the item to look for
+fn distributions(property: Option<Property>) -> &'static [(&'static str, &'static str)] {
+    match property {
+        Some(Property::Search) => &[
+            (
+                "present",
+                "{ let input = input(n, false); let item = input[n / 3]; (input, item) }",
+            ),
+            ("absent", "(input(n, false), -1)"),
+        ],
+        Some(Property::SortedSearch) => &[
+            (
+                "present",
+                "{ let input = input(n, true); let item = input[n / 3]; (input, item) }",
+            ),
+            ("absent", "(input(n, true), -1)"),
+        ],
+        _ => &[
+            ("random", "(input(n, false), -1)"),
+            ("sorted", "(input(n, true), -1)"),
+            (
+                "reversed",
+                "{ let mut input = input(n, true); input.reverse(); (input, -1) }",
+            ),
+            (
+                "duplicates",
+                "(input(n, false).into_iter().map(|x| x % 16).collect(), -1)",
+            ),
+        ],
+    }
+}
+
+// The source of a test harness timing the given expression, and the baselines
+// of the standard library matching the property of the exercise, on inputs of
+// a few sizes and distributions. It is appended to the source of the exercise
+// and compiled with optimizations, like the other harnesses
+pub fn harness(call: &str, property: Option<Property>) -> String {
+    let candidates: Vec<(&str, &str)> = Some((YOURS, call))
+        .into_iter()
+        .chain(baselines(property).iter().copied())
+        .collect();
+    let timed: String = candidates
+        .iter()
+        .map(|(name, call)| {
+            format!(
+                r#"
+                let nanos = time(|| {{
+                    #[allow(unused_mut)]
+                    let mut input = prepared.clone();
+                    #[allow(unused_variables)]
+                    let item = item;
+                    let start = Instant::now();
+                    std::hint::black_box({call});
+                    let elapsed = start.elapsed();
+                    std::hint::black_box(&input);
+                    elapsed
+                }});
+                println!("{prefix} {{}} {{}} {name} {{}}", distribution, n, nanos);{slowest}"#,
+                call = call,
+                name = name,
+                prefix = TIMING_PREFIX,
+                // The larger sizes are skipped once the exercise itself is too slow
+                slowest = if *name == YOURS {
+                    "\n                slowest = nanos;"
+                } else {
+                    ""
+                },
+            )
+        })
+        .collect();
+    let prepared: String = distributions(property)
+        .iter()
+        .map(|(name, prepare)| format!("\n                    {:?} => {},", name, prepare))
+        .collect();
+    let names: Vec<String> = distributions(property)
+        .iter()
+        .map(|(name, _)| format!("{:?}", name))
+        .collect();
+    let sizes: Vec<String> = SIZES.iter().map(|n| n.to_string()).collect();
+
+    format!(
+        r#"
+#[cfg(test)]
+mod __algo_bench {{
+    #[allow(unused_imports)]
+    use super::*;
+    use std::time::{{Duration, Instant}};
+
+{input_source}
+    // The fastest of a few batches of runs, in nanoseconds per run
+    fn time(mut run: impl FnMut() -> Duration) -> f64 {{
+        let started = Instant::now();
+        let (mut best, mut batches) = (f64::MAX, 0);
+        while batches == 0 || (batches < 5 && started.elapsed() < Duration::from_millis(50)) {{
+            let (mut total, mut runs) = (Duration::ZERO, 0u32);
+            while runs == 0 || total < Duration::from_millis(1) {{
+                total += run();
+                runs += 1;
+            }}
+            best = best.min(total.as_nanos() as f64 / f64::from(runs));
+            batches += 1;
+        }}
+        best
+    }}
+
+    #[test]
+    fn bench() {{
+        for &distribution in &[{names}] {{
+            for &n in &[{sizes}] {{
+                let (prepared, item): (Vec<i64>, i64) = match distribution {{{prepared}
+                    _ => unreachable!(),
+                }};
+                let mut slowest = 0.0;
+{timed}
+                if slowest > 10_000_000.0 {{
+                    break;
+                }}
+            }}
+        }}
+    }}
+}}
+"#,
+        input_source = INPUT_SOURCE,
+        names = names.join(", "),
+        sizes = sizes.join(", "),
+        prepared = prepared,
+        timed = timed,
+    )
+}
+
+// How long one implementation took on one input
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Timing {
+    // The kind of input, such as `random` or `present`
+    pub input: String,
+    // The size of the input
+    pub n: usize,
+    // The implementation, `yours` or the name of a function of the standard library
+    pub candidate: String,
+    // How long a single run took, in nanoseconds
+    pub nanos: f64,
+}
+
+// Parse the timings printed by the harness. The first one follows
+// the name of the test on the same line
+pub fn parse_timings(stdout: &str) -> Vec<Timing> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(TIMING_PREFIX)?;
+            let mut words = rest.split_whitespace();
+            Some(Timing {
+                input: words.next()?.to_string(),
+                n: words.next()?.parse().ok()?,
+                candidate: words.next()?.to_string(),
+                nanos: words.next()?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+// A benchmark of an exercise, as recorded in the progress file
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Benchmark {
+    // The time of the benchmark, in seconds since the Unix epoch
+    pub at: u64,
+    // The hash of the source code which was benchmarked
+    pub hash: Option<String>,
+    pub timings: Vec<Timing>,
+}
+
+impl Benchmark {
+    // How long the exercise itself took on the given input
+    fn yours(&self, input: &str, n: usize) -> Option<f64> {
+        self.timing(input, n, YOURS)
+    }
+
+    fn timing(&self, input: &str, n: usize, candidate: &str) -> Option<f64> {
+        self.timings
+            .iter()
+            .find(|t| t.input == input && t.n == n && t.candidate == candidate)
+            .map(|t| t.nanos)
+    }
+}
+
+// Benchmark the given exercise against the standard library and show the
+// timings next to the ones of its previous benchmark. The exercise has to
+// pass first, there is no point in timing a wrong answer.
+// The benchmark is recorded in the given progress, even if it stopped early
+pub fn bench(exercise: &Exercise, progress: &mut Progress) -> Result<(), ()> {
+    let call = match &exercise.call {
+        Some(call) => call,
+        None => {
+            warn!("{} has no call to benchmark", exercise);
+            println!("Declare the `call` expression running it in info.toml.");
+            return Err(());
+        }
+    };
+
+    let progress_bar = ProgressBar::new_spinner();
+    progress_bar.set_message(format!("Benchmarking {}...", exercise).as_str());
+    progress_bar.enable_steady_tick(100);
+    let passed = exercise.compile().and_then(|compiled| compiled.run());
+    let output = passed.and_then(|_| {
+        exercise.run_harness(&harness(call, exercise.property), HARNESS_TEST, "bench")
+    });
+    progress_bar.finish_and_clear();
+
+    // The timings printed before a crash, such as a stack overflow, are still shown
+    let (timings, failure) = match output {
+        Ok(output) => (parse_timings(&output.stdout), None),
+        Err(output) => (parse_timings(&output.stdout), Some(output)),
+    };
+    if timings.is_empty() {
+        warn!("{} could not be benchmarked", exercise);
+        print_failure(&failure.unwrap_or_default());
+        return Err(());
+    }
+    let benchmark = Benchmark {
+        at: now(),
+        hash: exercise.source_hash().ok(),
+        timings,
+    };
+    let previous = progress.benchmarks(exercise).last().cloned();
+
+    success!("Benchmarked {}, compiled with optimizations", exercise);
+    println!();
+    print_table(&benchmark, previous.as_ref(), exercise.property);
+    let measured = benchmark
+        .timings
+        .iter()
+        .filter(|t| t.candidate == YOURS)
+        .count();
+    if let Some(failure) = &failure {
+        warn!("{} stopped before the end of the benchmark", exercise);
+        print_failure(failure);
+    } else if measured < distributions(exercise.property).len() * SIZES.len() {
+        println!("The larger inputs were skipped, your version took too long on them.");
+    }
+    progress.record_benchmark(exercise, benchmark);
+    if let Err(e) = progress.save() {
+        warn!("Could not save your progress: {}", e);
+    }
+    match failure {
+        Some(_) => Err(()),
+        None => Ok(()),
+    }
+}
+
+fn print_failure(output: &ExerciseOutput) {
+    if !output.diagnostics.is_empty() {
+        print_diagnostics(&output.diagnostics);
+    } else if let Some(message) = panic_message(&output.stderr) {
+        println!("{}", message);
+    } else {
+        println!("{}", output.stderr);
+    }
+}
+
+// Show the timings of the exercise next to its previous ones, and the ones of
+// the standard library along with how many times longer the exercise took
+fn print_table(benchmark: &Benchmark, previous: Option<&Benchmark>, property: Option<Property>) {
+    let baselines = baselines(property);
+    let mut header = vec![
+        String::from("Input"),
+        String::from("n"),
+        String::from(YOURS),
+    ];
+    if previous.is_some() {
+        header.push(String::from("previous"));
+    }
+    header.extend(baselines.iter().map(|(name, _)| name.to_string()));
+
+    // Every cell, with whether it shows an improvement or a regression
+    let mut rows: Vec<Vec<(String, Option<bool>)>> = Vec::new();
+    for timing in benchmark.timings.iter().filter(|t| t.candidate == YOURS) {
+        let mut row = vec![
+            (timing.input.clone(), None),
+            (timing.n.to_string(), None),
+            (format_nanos(timing.nanos), None),
+        ];
+        if let Some(previous) = previous {
+            row.push(match previous.yours(&timing.input, timing.n) {
+                Some(nanos) => {
+                    let change = (timing.nanos - nanos) / nanos * 100.0;
+                    (
+                        format!("{} ({:+.0}%)", format_nanos(nanos), change),
+                        Some(change <= 0.0),
+                    )
+                }
+                None => (String::from("-"), None),
+            });
+        }
+        for (name, _) in baselines {
+            row.push(match benchmark.timing(&timing.input, timing.n, name) {
+                Some(nanos) => (
+                    format!("{} x{:.1}", format_nanos(nanos), timing.nanos / nanos),
+                    None,
+                ),
+                None => (String::from("-"), None),
+            });
+        }
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].0.chars().count())
+                .chain(Some(header[column].len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let header: Vec<String> = header
+        .iter()
+        .zip(&widths)
+        .map(|(title, &width)| format!("{:width$}", title, width = width))
+        .collect();
+    println!("{}", header.join("  ").trim_end());
+    for row in rows {
+        let cells: Vec<String> = row
+            .into_iter()
+            .zip(&widths)
+            .map(|((cell, improved), &width)| {
+                let cell = format!("{:width$}", cell, width = width);
+                match improved {
+                    Some(true) => style(cell).green().to_string(),
+                    Some(false) => style(cell).red().to_string(),
+                    None => cell,
+                }
+            })
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+    println!();
+    if !baselines.is_empty() {
+        println!("Next to the standard library, `x2.0` means your version takes twice as long.");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_timings() {
+        let stdout = "running 1 test\n\
+                      test __algo_bench::bench ... algo-bench random 1000 yours 15300.5\n\
+                      algo-bench random 1000 sort_unstable 4100\n\
+                      algo-bench random 10000 yours 201000\n\
+                      test __algo_bench::bench ... ok\n";
+        let timings = parse_timings(stdout);
+        assert_eq!(timings.len(), 3);
+        assert_eq!(timings[0].candidate, YOURS);
+        assert_eq!(timings[0].nanos, 15300.5);
+        let benchmark = Benchmark {
+            at: 0,
+            hash: None,
+            timings,
+        };
+        assert_eq!(benchmark.yours("random", 10000), Some(201000.0));
+        assert_eq!(
+            benchmark.timing("random", 1000, "sort_unstable"),
+            Some(4100.0)
+        );
+        assert_eq!(benchmark.yours("sorted", 1000), None);
+    }
+}
@@ -0,0 +1,260 @@
+use crate::complexity::ComplexityReport;
+use crate::exercise::{case_inputs, has_marker, Exercise, ExerciseList};
+use crate::support::support_args;
+use indicatif::ProgressBar;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Check that the exercise list loaded from the given manifest and the exercise tree agree:
+// every listed exercise exists, is unique, is marked as not done, has a hint
+// and belongs to a declared section if any, comes after its prerequisites,
+// every exercise source under the declared root is listed, the support library builds,
+// every stub fails and every reference solution passes.
+// Each problem found is reported to the end user. A reference solution whose
+// running time grows faster than declared is only warned about, since timings vary
+pub fn check_content(list: &ExerciseList, manifest: &Path) -> Result<(), ()> {
+    let exercises = &list.exercises;
+    let mut problems = Vec::new();
+    let mut warnings = Vec::new();
+
+    let mut section_names = HashSet::new();
+    for section in &list.sections {
+        if !section_names.insert(section.name.as_str()) {
+            problems.push(format!(
+                "The section `{}` is declared more than once",
+                section.name
+            ));
+        }
+    }
+
+    let mut names = HashSet::new();
+    let mut listed = HashSet::new();
+    for exercise in exercises {
+        if !names.insert(exercise.name.as_str()) {
+            problems.push(format!(
+                "The name `{}` is used more than once",
+                exercise.name
+            ));
+        }
+        for name in &exercise.requires {
+            if !names.contains(name.as_str()) {
+                problems.push(format!(
+                    "{} requires {}, which should come before it in {}",
+                    exercise,
+                    name,
+                    manifest.display()
+                ));
+            }
+        }
+        if let Some(section) = &exercise.section {
+            if !section_names.contains(section.as_str()) {
+                problems.push(format!(
+                    "{} belongs to the section `{}`, which is not declared in [[sections]]",
+                    exercise, section
+                ));
+            }
+        }
+        match exercise.hints() {
+            Ok(levels) if levels.is_empty() || levels.iter().any(|l| hint_is_empty(l)) => {
+                problems.push(format!("{} has an empty hint", exercise))
+            }
+            Ok(_) => {}
+            Err(e) => problems.push(format!("The hints of {} cannot be read: {}", exercise, e)),
+        }
+
+        match fs::read_to_string(&exercise.path) {
+            Ok(source) => {
+                if !has_marker(&source) {
+                    problems.push(format!("{} has no `I AM NOT DONE` marker", exercise));
+                }
+                if let Ok(path) = exercise.path.canonicalize() {
+                    listed.insert(path);
+                }
+            }
+            Err(e) => problems.push(format!("{} cannot be read: {}", exercise, e)),
+        }
+
+        for file in [&exercise.stdin_file, &exercise.expected_output_file]
+            .iter()
+            .copied()
+            .flatten()
+        {
+            if !file.exists() {
+                problems.push(format!(
+                    "The file {} of {} does not exist",
+                    file.display(),
+                    exercise
+                ));
+            }
+        }
+
+        if let Some(cases) = &exercise.cases {
+            match case_inputs(cases) {
+                Ok(inputs) if inputs.is_empty() => {
+                    problems.push(format!("{} has no case in {}", exercise, cases.display()))
+                }
+                Ok(inputs) => {
+                    for input in inputs.iter().filter(|i| !i.with_extension("out").exists()) {
+                        problems.push(format!(
+                            "The case {} of {} has no expected output",
+                            input.display(),
+                            exercise
+                        ));
+                    }
+                }
+                Err(e) => problems.push(format!("{} cannot be read: {}", cases.display(), e)),
+            }
+        }
+
+        let checked = exercise.complexity.is_some()
+            || exercise.comparisons.is_some()
+            || exercise.stable
+            || exercise.property.is_some();
+        if checked && exercise.call.is_none() {
+            problems.push(format!(
+                "{} declares a complexity, comparisons, stability or property but no call",
+                exercise
+            ));
+        }
+
+        if let Some(solution) = &exercise.solution {
+            if !solution.exists() {
+                problems.push(format!(
+                    "The solution {} of {} does not exist",
+                    solution.display(),
+                    exercise
+                ));
+            }
+        }
+    }
+
+    // A track without a root may pick a few of the exercises of a directory
+    if let Some(root) = &list.root {
+        let mut sources = Vec::new();
+        if let Err(e) = collect_sources(root, &mut sources) {
+            problems.push(format!("{} cannot be read: {}", root.display(), e));
+        }
+        sources.sort();
+        // The support library is shared by the exercises rather than one of them
+        let support = root.join("support");
+        for source in sources.into_iter().filter(|s| !s.starts_with(&support)) {
+            let path = source.canonicalize().unwrap_or_else(|_| source.clone());
+            if !listed.contains(&path) {
+                problems.push(format!(
+                    "{} is not listed in {}",
+                    source.display(),
+                    manifest.display()
+                ));
+            }
+        }
+    }
+
+    if let Err(output) = support_args() {
+        problems.push(output.stderr.trim_end().to_string());
+    }
+
+    for exercise in exercises.iter().filter(|e| e.path.exists()) {
+        if passes(exercise) {
+            problems.push(format!("The stub {} already passes", exercise));
+        }
+
+        if let Some(solution) = exercise.solution.as_ref().filter(|s| s.exists()) {
+            let reference = Exercise {
+                path: solution.clone(),
+                ..exercise.clone()
+            };
+            if !passes(&reference) {
+                problems.push(format!("The solution {} does not pass", reference));
+            } else if let Some(report) = too_slow(&reference) {
+                warnings.push(format!(
+                    "The solution {} grows like O({}), the declared complexity is O({})",
+                    reference, report.fitted, report.declared
+                ));
+            }
+        }
+    }
+
+    for warning in &warnings {
+        warn!("{}", warning);
+    }
+    if problems.is_empty() {
+        success!("The content of all {} exercises is valid!", exercises.len());
+        return Ok(());
+    }
+
+    for problem in &problems {
+        warn!("{}", problem);
+    }
+    println!();
+    println!("Found {} problems in the exercise content.", problems.len());
+    Err(())
+}
+
+// Whether the given hint has any content beyond the `Hint:` prefix
+fn hint_is_empty(hint: &str) -> bool {
+    let hint = hint.trim();
+    hint.strip_prefix("Hint:").unwrap_or(hint).trim().is_empty()
+}
+
+fn spinner(exercise: &Exercise) -> ProgressBar {
+    let progress_bar = ProgressBar::new_spinner();
+    progress_bar.set_message(format!("Checking {}...", exercise).as_str());
+    progress_bar.enable_steady_tick(100);
+    progress_bar
+}
+
+// Whether the given exercise compiles and runs successfully under its mode,
+// like its reference, stably and within its declared comparisons.
+// Its running time is left to `too_slow`
+fn passes(exercise: &Exercise) -> bool {
+    let progress_bar = spinner(exercise);
+    let passed = exercise
+        .compile()
+        .is_ok_and(|compilation| compilation.run().is_ok())
+        && exercise
+            .check_property()
+            .is_none_or(|output| output.is_ok())
+        && exercise
+            .check_stability()
+            .is_none_or(|output| output.is_ok())
+        && exercise
+            .count_comparisons()
+            .is_none_or(|report| report.is_ok_and(|report| !report.exceeds()));
+    progress_bar.finish_and_clear();
+    passed
+}
+
+// The measurements of the given exercise, if its running time grows faster than declared
+fn too_slow(exercise: &Exercise) -> Option<ComplexityReport> {
+    exercise.complexity?;
+    let progress_bar = spinner(exercise);
+    let report = exercise.measure_complexity().and_then(Result::ok);
+    progress_bar.finish_and_clear();
+    report.filter(ComplexityReport::exceeds)
+}
+
+pub fn collect_sources(dir: &Path, sources: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_sources(&path, sources)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            sources.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hint_is_empty() {
+        assert!(hint_is_empty(""));
+        assert!(hint_is_empty("\nHint: "));
+        assert!(!hint_is_empty("Hint: split the array in two halves"));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::complexity::Class;
+use crate::harness::INPUT_SOURCE;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+// The name of the test which counts the comparisons of an exercise
+pub const HARNESS_TEST: &str = "__algo_comparisons::count";
+// The prefix of the lines the harness prints, one per input
+const COUNT_PREFIX: &str = "algo-comparisons";
+// The number of elements the comparisons are counted on
+const SIZE: usize = 1024;
+// The `Counted` wrapper the elements are made of
+const COUNTED_SOURCE: &str = include_str!("../harness/counted.rs");
+
+// An upper bound on the number of comparisons, such as `2 n log n`
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+#[serde(try_from = "String", into = "String")]
+pub struct Bound {
+    pub factor: f64,
+    pub class: Class,
+}
+
+impl Bound {
+    // The number of comparisons allowed for n elements
+    pub fn limit(self, n: usize) -> u64 {
+        (self.factor * self.class.growth(n as f64)).ceil() as u64
+    }
+}
+
+impl Display for Bound {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.factor == 1.0 {
+            write!(f, "{}", self.class)
+        } else {
+            write!(f, "{} {}", self.factor, self.class)
+        }
+    }
+}
+
+impl FromStr for Bound {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Bound, String> {
+        let s = s.trim();
+        let (factor, class) = match s.split_once(' ') {
+            Some((factor, class)) if factor.parse::<f64>().is_ok() => {
+                (factor.parse().unwrap(), class)
+            }
+            _ => (1.0, s),
+        };
+        Ok(Bound {
+            factor,
+            class: class.parse()?,
+        })
+    }
+}
+
+impl TryFrom<String> for Bound {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Bound, String> {
+        s.parse()
+    }
+}
+
+impl From<Bound> for String {
+    fn from(bound: Bound) -> String {
+        bound.to_string()
+    }
+}
+
+// The source of a test harness counting the comparisons the given expression
+// makes, to append to the source of the exercise. The expression is evaluated
+// with a `Vec<Counted<i64>>` named `input`, once with random and once with
+// sorted numbers, see `harness/counted.rs`. The `item` of searches isn't one of them
+pub fn harness(call: &str) -> String {
+    format!(
+        r#"
+#[cfg(test)]
+mod __algo_comparisons {{
+    #[allow(unused_imports)]
+    use super::*;
+
+{counted_source}
+{input_source}
+    #[test]
+    fn count() {{
+        for &(name, sorted) in &[("random", false), ("sorted", true)] {{
+            #[allow(unused_mut)]
+            let mut input: Vec<Counted<i64>> =
+                input({size}, sorted).into_iter().map(Counted).collect();
+            #[allow(unused_variables)]
+            let item = Counted(-1i64);
+            reset_comparisons();
+            std::hint::black_box({call});
+            println!("{prefix} {{}} {{}}", name, comparisons());
+        }}
+    }}
+}}
+"#,
+        counted_source = COUNTED_SOURCE,
+        input_source = INPUT_SOURCE,
+        size = SIZE,
+        call = call,
+        prefix = COUNT_PREFIX,
+    )
+}
+
+// The number of comparisons made on one kind of input
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct Count {
+    // The kind of input, `random` or `sorted`
+    pub input: String,
+    pub comparisons: u64,
+}
+
+// The comparisons an exercise made, compared with its declared bound
+#[derive(Serialize, Clone, Debug)]
+pub struct ComparisonReport {
+    pub bound: Bound,
+    // The number of elements of every input
+    pub n: usize,
+    pub counts: Vec<Count>,
+}
+
+impl ComparisonReport {
+    // Parse the counts printed by the harness.
+    // Returns None if the harness didn't print any
+    pub fn new(bound: Bound, stdout: &str) -> Option<ComparisonReport> {
+        let counts: Vec<Count> = stdout
+            .lines()
+            .filter_map(|line| {
+                let (_, rest) = line.split_once(COUNT_PREFIX)?;
+                let mut words = rest.split_whitespace();
+                Some(Count {
+                    input: words.next()?.to_string(),
+                    comparisons: words.next()?.parse().ok()?,
+                })
+            })
+            .collect();
+        if counts.is_empty() {
+            return None;
+        }
+        Some(ComparisonReport {
+            bound,
+            n: SIZE,
+            counts,
+        })
+    }
+
+    // Whether any of the inputs needed more comparisons than the bound allows
+    pub fn exceeds(&self) -> bool {
+        let limit = self.bound.limit(self.n);
+        self.counts.iter().any(|count| count.comparisons > limit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_bound() {
+        let bound: Bound = "2 n log n".parse().unwrap();
+        assert_eq!(bound.factor, 2.0);
+        assert_eq!(bound.class, Class::Linearithmic);
+        assert_eq!(bound.limit(1024), 20480);
+        assert_eq!("n^2".parse::<Bound>().unwrap().to_string(), "n^2");
+        assert!("2 n!".parse::<Bound>().is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let stdout = "running 1 test\n\
+                      test __algo_comparisons::count ... algo-comparisons random 9000\n\
+                      algo-comparisons sorted 523776\n";
+        let report = ComparisonReport::new("2 n log n".parse().unwrap(), stdout).unwrap();
+        assert_eq!(report.counts.len(), 2);
+        assert_eq!(report.counts[1].comparisons, 523776);
+        assert!(report.exceeds());
+        let report = ComparisonReport::new("n^2".parse().unwrap(), stdout).unwrap();
+        assert!(!report.exceeds());
+    }
+}
@@ -0,0 +1,378 @@
+use crate::harness::INPUT_SOURCE;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+// The name of the test which measures the running time of an exercise
+pub const HARNESS_TEST: &str = "__algo_complexity::measure";
+// The prefix of the lines the harness prints, one per input size
+const MEASUREMENT_PREFIX: &str = "algo-complexity";
+// How much faster than its declared class the running time may grow,
+// as a difference of exponents, before the exercise fails
+const TOLERANCE: f64 = 0.5;
+// The width of the bars of the chart, in characters
+const CHART_WIDTH: usize = 40;
+
+// A complexity class, the growth of the running time with the size of the input
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(try_from = "String", into = "String")]
+pub enum Class {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+const CLASSES: &[Class] = &[
+    Class::Constant,
+    Class::Logarithmic,
+    Class::Linear,
+    Class::Linearithmic,
+    Class::Quadratic,
+    Class::Cubic,
+];
+
+impl Class {
+    // The running time of an input of size n, up to a constant factor
+    pub fn growth(self, n: f64) -> f64 {
+        match self {
+            Class::Constant => 1.0,
+            Class::Logarithmic => n.log2(),
+            Class::Linear => n,
+            Class::Linearithmic => n * n.log2(),
+            Class::Quadratic => n * n,
+            Class::Cubic => n * n * n,
+        }
+    }
+
+    // The exponent of the class over the given input sizes, as seen on a log-log scale
+    fn exponent(self, sizes: &[f64]) -> f64 {
+        let points: Vec<(f64, f64)> = sizes.iter().map(|&n| (n, self.growth(n))).collect();
+        log_log_slope(&points)
+    }
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(match self {
+            Class::Constant => "1",
+            Class::Logarithmic => "log n",
+            Class::Linear => "n",
+            Class::Linearithmic => "n log n",
+            Class::Quadratic => "n^2",
+            Class::Cubic => "n^3",
+        })
+    }
+}
+
+impl FromStr for Class {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Class, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.join(" ").as_str() {
+            "1" => Ok(Class::Constant),
+            "log n" => Ok(Class::Logarithmic),
+            "n" => Ok(Class::Linear),
+            "n log n" => Ok(Class::Linearithmic),
+            "n^2" | "n²" => Ok(Class::Quadratic),
+            "n^3" | "n³" => Ok(Class::Cubic),
+            _ => Err(format!("unknown complexity class: {}", s)),
+        }
+    }
+}
+
+impl TryFrom<String> for Class {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Class, String> {
+        s.parse()
+    }
+}
+
+impl From<Class> for String {
+    fn from(class: Class) -> String {
+        class.to_string()
+    }
+}
+
+// The input the expression of an exercise is measured with
+#[derive(Deserialize, Copy, Clone, PartialEq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Input {
+    // Pseudo-random numbers, the same ones at every run, see `harness/input.rs`
+    #[default]
+    Random,
+    // The same numbers in ascending order
+    Sorted,
+}
+
+// The source of a test harness measuring how long the given expression takes
+// for inputs of growing sizes, to append to the source of the exercise.
+// The expression is evaluated with a `Vec<i64>` of n numbers named `input`,
+// and an `item` which is not one of them for searches.
+// Sizes double until the time budget of the harness is spent
+pub fn harness(call: &str, input: Input) -> String {
+    format!(
+        r#"
+#[cfg(test)]
+mod __algo_complexity {{
+    #[allow(unused_imports)]
+    use super::*;
+    use std::time::{{Duration, Instant}};
+
+{input_source}
+    #[test]
+    fn measure() {{
+        let started = Instant::now();
+        let mut n = 64;
+        while n <= 1 << 20 && started.elapsed() < Duration::from_millis(1500) {{
+            let prepared = input(n, {sorted});
+            let size_started = Instant::now();
+            // The fastest of a few batches, so that being preempted doesn't count
+            let (mut best, mut batches) = (u128::MAX, 0);
+            while batches == 0
+                || (batches < 5 && size_started.elapsed() < Duration::from_millis(50))
+            {{
+                let (mut total, mut runs) = (Duration::ZERO, 0u32);
+                while runs == 0 || total < Duration::from_millis(1) {{
+                    #[allow(unused_mut)]
+                    let mut input = prepared.clone();
+                    #[allow(unused_variables)]
+                    let item: i64 = -1;
+                    let start = Instant::now();
+                    std::hint::black_box({call});
+                    total += start.elapsed();
+                    std::hint::black_box(&input);
+                    runs += 1;
+                }}
+                best = best.min(total.as_nanos() / u128::from(runs));
+                batches += 1;
+            }}
+            println!("{prefix} {{}} {{}}", n, best);
+            if best > 200_000_000 {{
+                break;
+            }}
+            n *= 2;
+        }}
+    }}
+}}
+"#,
+        input_source = INPUT_SOURCE,
+        call = call,
+        sorted = input == Input::Sorted,
+        prefix = MEASUREMENT_PREFIX,
+    )
+}
+
+// The running time of the exercise for an input of a given size
+#[derive(Serialize, Copy, Clone, PartialEq, Debug)]
+pub struct Measurement {
+    // The size of the input
+    pub n: usize,
+    // How long a single evaluation took, in nanoseconds
+    pub nanos: f64,
+}
+
+// Parse the measurements printed by the harness. The first one follows
+// the name of the test on the same line
+pub fn parse_measurements(stdout: &str) -> Vec<Measurement> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(MEASUREMENT_PREFIX)?;
+            let mut words = rest.split_whitespace();
+            Some(Measurement {
+                n: words.next()?.parse().ok()?,
+                nanos: words.next()?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+// How the running time of an exercise grows, compared with its declared class
+#[derive(Serialize, Clone, Debug)]
+pub struct ComplexityReport {
+    // The class declared in info.toml
+    pub declared: Class,
+    // The class closest to the measured growth
+    pub fitted: Class,
+    // The measured exponent of the running time, on a log-log scale
+    pub exponent: f64,
+    // The exponent of the declared class over the same input sizes
+    pub declared_exponent: f64,
+    pub measurements: Vec<Measurement>,
+}
+
+impl ComplexityReport {
+    // Fit the given measurements. The smallest inputs mostly measure
+    // the overhead of the call, so only the larger half of them is fitted.
+    // Returns None if there are too few measurements to fit
+    pub fn new(declared: Class, measurements: Vec<Measurement>) -> Option<ComplexityReport> {
+        let fitted = &measurements[measurements.len() / 2..];
+        if fitted.len() < 3 {
+            return None;
+        }
+        let points: Vec<(f64, f64)> = fitted
+            .iter()
+            .map(|m| (m.n as f64, m.nanos.max(1.0)))
+            .collect();
+        let sizes: Vec<f64> = points.iter().map(|&(n, _)| n).collect();
+        let exponent = log_log_slope(&points);
+        let closest = CLASSES
+            .iter()
+            .copied()
+            .min_by(|a, b| {
+                let a = (a.exponent(&sizes) - exponent).abs();
+                let b = (b.exponent(&sizes) - exponent).abs();
+                a.partial_cmp(&b).unwrap()
+            })
+            .unwrap();
+        Some(ComplexityReport {
+            declared,
+            fitted: closest,
+            exponent,
+            declared_exponent: declared.exponent(&sizes),
+            measurements,
+        })
+    }
+
+    // Whether the running time clearly grows faster than the declared class
+    pub fn exceeds(&self) -> bool {
+        self.fitted > self.declared && self.exponent > self.declared_exponent + TOLERANCE
+    }
+}
+
+// The slope of the least squares line through the given points on a log-log scale
+fn log_log_slope(points: &[(f64, f64)]) -> f64 {
+    let logs: Vec<(f64, f64)> = points.iter().map(|&(x, y)| (x.ln(), y.ln())).collect();
+    let count = logs.len() as f64;
+    let mean_x = logs.iter().map(|&(x, _)| x).sum::<f64>() / count;
+    let mean_y = logs.iter().map(|&(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = logs.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    if variance == 0.0 {
+        0.0
+    } else {
+        covariance / variance
+    }
+}
+
+// Draw the measured running time of every input size as a bar, on a log scale.
+// The `|` marks the running time the declared class predicts
+// from the first fitted measurement
+pub fn chart(report: &ComplexityReport) -> String {
+    let measurements = &report.measurements;
+    let anchor = match measurements.get(measurements.len() / 2) {
+        Some(anchor) => anchor,
+        None => return String::new(),
+    };
+    let predicted = |n: usize| {
+        anchor.nanos * report.declared.growth(n as f64) / report.declared.growth(anchor.n as f64)
+    };
+    let nanos = measurements.iter().map(|m| m.nanos.max(1.0));
+    let min = nanos.clone().fold(f64::INFINITY, f64::min);
+    let max = nanos.fold(1.0, f64::max);
+    let column = |nanos: f64| {
+        let ratio = (nanos / min).ln() / (max / min).ln().max(f64::EPSILON);
+        (ratio * CHART_WIDTH as f64).round()
+    };
+
+    let mut chart = format!("{:>9}  {:>10}\n", "n", "time");
+    for measurement in measurements {
+        let length = column(measurement.nanos).max(1.0) as usize;
+        let mut bar: Vec<char> = "#".repeat(length).chars().collect();
+        let marker = column(predicted(measurement.n));
+        if (0.0..=CHART_WIDTH as f64).contains(&marker) {
+            let marker = marker as usize;
+            if bar.len() <= marker {
+                bar.resize(marker + 1, ' ');
+            }
+            bar[marker] = '|';
+        }
+        chart.push_str(&format!(
+            "{:>9}  {:>10}  {}\n",
+            measurement.n,
+            format_nanos(measurement.nanos),
+            bar.into_iter().collect::<String>()
+        ));
+    }
+    chart.push_str(&format!(
+        "{:>9}  {:>10}  # measured (log scale), | expected for O({})\n",
+        "", "", report.declared
+    ));
+    chart
+}
+
+// A duration in nanoseconds, in the most readable unit
+pub fn format_nanos(nanos: f64) -> String {
+    match nanos {
+        nanos if nanos < 1e3 => format!("{:.0} ns", nanos),
+        nanos if nanos < 1e6 => format!("{:.1} us", nanos / 1e3),
+        nanos if nanos < 1e9 => format!("{:.1} ms", nanos / 1e6),
+        nanos => format!("{:.2} s", nanos / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn measurements(class: Class) -> Vec<Measurement> {
+        (6..16)
+            .map(|power| {
+                let n = 1 << power;
+                Measurement {
+                    n,
+                    nanos: 3.0 * class.growth(n as f64),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_class() {
+        assert_eq!("n log n".parse(), Ok(Class::Linearithmic));
+        assert_eq!(" n^2 ".parse(), Ok(Class::Quadratic));
+        assert!("n!".parse::<Class>().is_err());
+    }
+
+    #[test]
+    fn test_parse_measurements() {
+        let stdout = "running 1 test\nalgo-complexity 64 1200\nalgo-complexity 128 2500\n";
+        assert_eq!(
+            parse_measurements(stdout),
+            vec![
+                Measurement {
+                    n: 64,
+                    nanos: 1200.0
+                },
+                Measurement {
+                    n: 128,
+                    nanos: 2500.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fit() {
+        for &class in CLASSES {
+            let report = ComplexityReport::new(class, measurements(class)).unwrap();
+            assert_eq!(report.fitted, class);
+            assert!(!report.exceeds());
+        }
+
+        let report =
+            ComplexityReport::new(Class::Linearithmic, measurements(Class::Quadratic)).unwrap();
+        assert_eq!(report.fitted, Class::Quadratic);
+        assert!(report.exceeds());
+
+        let report = ComplexityReport::new(Class::Linear, measurements(Class::Linearithmic));
+        assert!(!report.unwrap().exceeds());
+        assert!(ComplexityReport::new(Class::Linear, Vec::new()).is_none());
+    }
+}
@@ -0,0 +1,432 @@
+use crate::error::Error;
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::process::Command;
+
+// The severity of a compiler diagnostic
+#[derive(Serialize, Copy, Clone, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl From<&str> for Level {
+    fn from(level: &str) -> Level {
+        match level {
+            "warning" => Level::Warning,
+            "note" | "failure-note" => Level::Note,
+            "help" => Level::Help,
+            _ => Level::Error,
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(match self {
+            Level::Error => "error",
+            Level::Warning => "warning",
+            Level::Note => "note",
+            Level::Help => "help",
+        })
+    }
+}
+
+// A message of the compiler about the code of an exercise
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    pub level: Level,
+    // The error code, such as E0308, if the message has one
+    pub code: Option<String>,
+    pub message: String,
+    // The pieces of code the message is about
+    pub spans: Vec<Span>,
+    // Additional notes and help which don't come with a change to the code
+    pub notes: Vec<Note>,
+    // Changes to the code proposed by the compiler
+    pub suggestions: Vec<Suggestion>,
+}
+
+// A piece of code a diagnostic points at
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct Span {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    // Whether this is the main location of the diagnostic
+    pub primary: bool,
+    pub label: Option<String>,
+    // The source lines covered by the span, along with the highlighted columns
+    pub lines: Vec<SpanLine>,
+}
+
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct SpanLine {
+    pub text: String,
+    pub highlight_start: usize,
+    pub highlight_end: usize,
+}
+
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct Note {
+    pub level: Level,
+    pub message: String,
+}
+
+// A change to the code proposed by the compiler
+#[derive(Serialize, Clone, PartialEq, Debug)]
+pub struct Suggestion {
+    pub message: String,
+    pub file: String,
+    pub line: usize,
+    pub replacement: String,
+}
+
+// The diagnostic format of rustc, see
+// https://doc.rust-lang.org/rustc/json.html
+#[derive(Deserialize)]
+struct RawDiagnostic {
+    message: String,
+    code: Option<RawCode>,
+    level: String,
+    spans: Vec<RawSpan>,
+    children: Vec<RawDiagnostic>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct RawSpan {
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+    text: Vec<RawSpanLine>,
+    label: Option<String>,
+    suggested_replacement: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawSpanLine {
+    text: String,
+    highlight_start: usize,
+    highlight_end: usize,
+}
+
+impl From<RawSpan> for Span {
+    fn from(span: RawSpan) -> Span {
+        Span {
+            file: span.file_name,
+            line: span.line_start,
+            column: span.column_start,
+            primary: span.is_primary,
+            label: span.label,
+            lines: span
+                .text
+                .into_iter()
+                .map(|line| SpanLine {
+                    text: line.text,
+                    highlight_start: line.highlight_start,
+                    highlight_end: line.highlight_end,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<RawDiagnostic> for Diagnostic {
+    fn from(raw: RawDiagnostic) -> Diagnostic {
+        let mut notes = Vec::new();
+        let mut suggestions = Vec::new();
+        for child in raw.children {
+            let replacements: Vec<&RawSpan> = child
+                .spans
+                .iter()
+                .filter(|span| span.suggested_replacement.is_some())
+                .collect();
+            if replacements.is_empty() {
+                notes.push(Note {
+                    level: Level::from(child.level.as_str()),
+                    message: child.message,
+                });
+                continue;
+            }
+            for span in replacements {
+                suggestions.push(Suggestion {
+                    message: child.message.clone(),
+                    file: span.file_name.clone(),
+                    line: span.line_start,
+                    replacement: span.suggested_replacement.clone().unwrap_or_default(),
+                });
+            }
+        }
+
+        Diagnostic {
+            level: Level::from(raw.level.as_str()),
+            code: raw.code.map(|code| code.code),
+            message: raw.message,
+            spans: raw.spans.into_iter().map(Span::from).collect(),
+            notes,
+            suggestions,
+        }
+    }
+}
+
+impl Diagnostic {
+    // The main location of the diagnostic, as `file:line:column`
+    pub fn location(&self) -> Option<String> {
+        self.spans
+            .iter()
+            .find(|span| span.primary)
+            .map(|span| format!("{}:{}:{}", span.file, span.line, span.column))
+    }
+
+    // The error code of the diagnostic if rustc can explain it.
+    // Lints have a code as well, but no explanation
+    pub fn explainable_code(&self) -> Option<&str> {
+        self.code
+            .as_deref()
+            .filter(|code| code.starts_with('E') && code[1..].chars().all(|c| c.is_ascii_digit()))
+    }
+
+    // The first line of the diagnostic, such as `error[E0308]: mismatched types`
+    pub fn headline(&self) -> String {
+        match &self.code {
+            Some(code) => format!("{}[{}]: {}", self.level, code, self.message),
+            None => format!("{}: {}", self.level, self.message),
+        }
+    }
+}
+
+// Parse the diagnostics printed by rustc with `--error-format=json`,
+// or by cargo with `--message-format=json`.
+// Returns the diagnostics along with the text rustc would have printed for them.
+// Summaries such as `aborting due to previous error` are left out
+pub fn parse_diagnostics(output: &str) -> (Vec<Diagnostic>, String) {
+    let mut diagnostics = Vec::new();
+    let mut rendered = String::new();
+    for line in output.lines().filter(|line| line.starts_with('{')) {
+        let value: serde_json::Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        let value = match value.get("reason") {
+            Some(reason) if reason == "compiler-message" => value["message"].clone(),
+            Some(_) => continue,
+            None => value,
+        };
+        let raw: RawDiagnostic = match serde_json::from_value(value) {
+            Ok(raw) => raw,
+            Err(_) => continue,
+        };
+        if let Some(text) = &raw.rendered {
+            rendered.push_str(text);
+        }
+        if raw.level == "failure-note" || is_summary(&raw) {
+            continue;
+        }
+        diagnostics.push(Diagnostic::from(raw));
+    }
+    (diagnostics, rendered)
+}
+
+// Whether the diagnostic only sums up the other ones
+fn is_summary(raw: &RawDiagnostic) -> bool {
+    raw.spans.is_empty()
+        && raw.code.is_none()
+        && (raw.message.starts_with("aborting due to") || raw.message.ends_with(" emitted"))
+}
+
+// Print a condensed view of the given diagnostics with the offending lines.
+// Warnings are only shown when there is no error to focus on
+pub fn print_diagnostics(diagnostics: &[Diagnostic]) {
+    let has_errors = diagnostics.iter().any(|d| d.level == Level::Error);
+    let shown: Vec<&Diagnostic> = diagnostics
+        .iter()
+        .filter(|d| !has_errors || d.level == Level::Error)
+        .collect();
+    for diagnostic in &shown {
+        print_diagnostic(diagnostic);
+        println!();
+    }
+
+    if let Some(code) = shown.iter().find_map(|d| d.explainable_code()) {
+        println!(
+            "To learn more about an error, run `algo explain {}`.",
+            style(code).bold()
+        );
+    }
+}
+
+fn print_diagnostic(diagnostic: &Diagnostic) {
+    let headline = diagnostic.headline();
+    match diagnostic.level {
+        Level::Error => println!("{}", style(headline).red().bold()),
+        Level::Warning => println!("{}", style(headline).yellow().bold()),
+        _ => println!("{}", style(headline).bold()),
+    }
+
+    // Only the spans in the file of the exercise are shown, not the ones
+    // pointing into the standard library
+    let file = diagnostic
+        .spans
+        .iter()
+        .find(|span| span.primary)
+        .map(|span| span.file.as_str());
+    let mut spans: Vec<&Span> = diagnostic
+        .spans
+        .iter()
+        .filter(|span| Some(span.file.as_str()) == file)
+        .filter(|span| span.primary || span.label.is_some())
+        .collect();
+    spans.sort_by_key(|span| (span.line, span.column));
+
+    let width = spans
+        .iter()
+        .map(|span| (span.line + span.lines.len()).to_string().len())
+        .max()
+        .unwrap_or(0);
+    let gutter = " ".repeat(width);
+    if let Some(location) = diagnostic.location() {
+        println!("{}{} {}", gutter, style("-->").blue().bold(), location);
+        println!("{} {}", gutter, style("|").blue());
+    }
+
+    // Several spans on the same line share a single copy of the line
+    let mut printed = 0;
+    for span in spans {
+        for (i, line) in span.lines.iter().enumerate() {
+            if span.line + i <= printed {
+                continue;
+            }
+            printed = span.line + i;
+            println!(
+                "{:>width$} {} {}",
+                style(span.line + i).blue().bold(),
+                style("|").blue(),
+                line.text,
+                width = width
+            );
+        }
+        if let Some(line) = span.lines.last() {
+            let start = line.highlight_start.saturating_sub(1);
+            let length = line
+                .highlight_end
+                .saturating_sub(line.highlight_start)
+                .max(1);
+            let marker = if span.primary { "^" } else { "-" };
+            let underline = format!(
+                "{} {}",
+                marker.repeat(length),
+                span.label.as_deref().unwrap_or("")
+            );
+            let underline = if span.primary {
+                style(underline.trim_end().to_string()).red().bold()
+            } else {
+                style(underline.trim_end().to_string()).blue()
+            };
+            println!(
+                "{} {} {}{}",
+                gutter,
+                style("|").blue(),
+                " ".repeat(start),
+                underline
+            );
+        }
+    }
+
+    for note in &diagnostic.notes {
+        println!(
+            "{} {} {}: {}",
+            gutter,
+            style("=").blue().bold(),
+            style(note.level).bold(),
+            note.message
+        );
+    }
+    for suggestion in &diagnostic.suggestions {
+        println!(
+            "{} {} {}: {}: `{}` (line {})",
+            gutter,
+            style("=").blue().bold(),
+            style("help").bold(),
+            suggestion.message,
+            style(&suggestion.replacement).green(),
+            suggestion.line
+        );
+    }
+}
+
+// Print the explanation of the given error code by rustc.
+// Why it cannot be explained is reported to the end user as well
+pub fn explain(code: &str) -> Result<(), Error> {
+    let output = match Command::new("rustc").args(["--explain", code]).output() {
+        Ok(output) => output,
+        Err(e) => {
+            let error = Error::spawn("rustc", e);
+            warn!("{}", error);
+            return Err(error);
+        }
+    };
+    if !output.status.success() {
+        let error = Error::NoExplanation {
+            code: code.to_string(),
+        };
+        warn!("{}", error);
+        return Err(error);
+    }
+    println!("{}", String::from_utf8_lossy(&output.stdout));
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const OUTPUT: &str = r#"{"$message_type":"diagnostic","message":"mismatched types","code":{"code":"E0308","explanation":"Expected type did not match the received type."},"level":"error","spans":[{"file_name":"sort.rs","byte_start":30,"byte_end":33,"line_start":2,"line_end":2,"column_start":18,"column_end":21,"is_primary":true,"text":[{"text":"    let x: i32 = \"a\";","highlight_start":18,"highlight_end":21}],"label":"expected `i32`, found `&str`","suggested_replacement":null,"suggestion_applicability":null,"expansion":null}],"children":[{"message":"try using a conversion method","code":null,"level":"help","spans":[{"file_name":"sort.rs","byte_start":30,"byte_end":33,"line_start":2,"line_end":2,"column_start":18,"column_end":21,"is_primary":true,"text":[],"label":null,"suggested_replacement":"\"a\".parse().unwrap()","suggestion_applicability":"MaybeIncorrect","expansion":null}],"children":[],"rendered":null}],"rendered":"error[E0308]: mismatched types\n"}
+{"$message_type":"diagnostic","message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[],"rendered":"error: aborting due to 1 previous error\n\n"}
+{"$message_type":"diagnostic","message":"For more information about this error, try `rustc --explain E0308`.","code":null,"level":"failure-note","spans":[],"children":[],"rendered":"For more information about this error, try `rustc --explain E0308`.\n"}
+"#;
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let (diagnostics, rendered) = parse_diagnostics(OUTPUT);
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.headline(), "error[E0308]: mismatched types");
+        assert_eq!(diagnostic.location(), Some(String::from("sort.rs:2:18")));
+        assert_eq!(
+            diagnostic.spans[0].label.as_deref(),
+            Some("expected `i32`, found `&str`")
+        );
+        assert_eq!(
+            diagnostic.suggestions,
+            vec![Suggestion {
+                message: String::from("try using a conversion method"),
+                file: String::from("sort.rs"),
+                line: 2,
+                replacement: String::from("\"a\".parse().unwrap()"),
+            }]
+        );
+        assert!(rendered.starts_with("error[E0308]: mismatched types\nerror: aborting"));
+    }
+
+    #[test]
+    fn test_parse_cargo_messages() {
+        let output = r#"{"reason":"compiler-artifact","package_id":"sort 0.0.1"}
+{"reason":"compiler-message","package_id":"sort 0.0.1","message":{"message":"unused variable: `x`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[],"children":[],"rendered":"warning: unused variable: `x`\n"}}
+"#;
+        let (diagnostics, _) = parse_diagnostics(output);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level, Level::Warning);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("unused_variables"));
+    }
+}
@@ -0,0 +1,213 @@
+use console::style;
+
+// A single line of the difference between two texts
+#[derive(PartialEq, Debug)]
+pub enum Line<'a> {
+    // A line present in both texts
+    Equal(&'a str),
+    // A line only present in the old text
+    Removed(&'a str),
+    // A line only present in the new text
+    Added(&'a str),
+}
+
+// Compute the line by line difference between two texts,
+// based on their longest common subsequence of lines
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(Line::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|line| Line::Removed(line)));
+    lines.extend(new[j..].iter().map(|line| Line::Added(line)));
+    lines
+}
+
+// Print the difference as two columns, the old text on the left
+// and the new text on the right, fitting in the given width
+pub fn print_side_by_side(lines: &[Line], old_title: &str, new_title: &str, width: usize) {
+    let column = width.saturating_sub(3) / 2;
+    println!(
+        "{} | {}",
+        style(fit(old_title, column)).bold(),
+        style(fit(new_title, column)).bold()
+    );
+    println!("{}", "=".repeat(column * 2 + 3));
+
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for line in lines {
+        match line {
+            Line::Removed(text) => removed.push(*text),
+            Line::Added(text) => added.push(*text),
+            Line::Equal(text) => {
+                print_changes(&mut removed, &mut added, column);
+                println!("{} | {}", fit(text, column), fit(text, column));
+            }
+        }
+    }
+    print_changes(&mut removed, &mut added, column);
+}
+
+// Print the pending removed and added lines next to each other
+fn print_changes(removed: &mut Vec<&str>, added: &mut Vec<&str>, column: usize) {
+    for i in 0..removed.len().max(added.len()) {
+        let left = fit(removed.get(i).copied().unwrap_or(""), column);
+        let right = fit(added.get(i).copied().unwrap_or(""), column);
+        println!(
+            "{} {} {}",
+            style(left).red(),
+            style("|").yellow(),
+            style(right).green()
+        );
+    }
+    removed.clear();
+    added.clear();
+}
+
+// Print the difference in the unified format, showing the given
+// number of unchanged lines around each change
+pub fn print_unified(lines: &[Line], old_title: &str, new_title: &str, context: usize) {
+    println!("{}", style(format!("--- {}", old_title)).red().bold());
+    println!("{}", style(format!("+++ {}", new_title)).green().bold());
+    for line in unified(lines, context) {
+        match line.chars().next() {
+            Some('@') => println!("{}", style(line).cyan()),
+            Some('-') => println!("{}", style(line).red()),
+            Some('+') => println!("{}", style(line).green()),
+            _ => println!("{}", line),
+        }
+    }
+}
+
+// The hunks of the unified format, without the file headers
+fn unified(lines: &[Line], context: usize) -> Vec<String> {
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Line::Equal(_)))
+        .map(|(i, _)| i)
+        .collect();
+
+    // Group the changes which are close enough to share their context
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changes {
+        let start = i.saturating_sub(context);
+        let end = (i + context + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut output = Vec::new();
+    for (start, end) in hunks {
+        let old_start = 1 + lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let new_start = 1 + lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+        let hunk = &lines[start..end];
+        let old_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+        output.push(format!(
+            "@@ -{},{} +{},{} @@",
+            old_start, old_count, new_start, new_count
+        ));
+        output.extend(hunk.iter().map(|line| match line {
+            Line::Equal(text) => format!(" {}", text),
+            Line::Removed(text) => format!("-{}", text),
+            Line::Added(text) => format!("+{}", text),
+        }));
+    }
+    output
+}
+
+// Pad or truncate the given text to exactly the given number of characters
+fn fit(text: &str, width: usize) -> String {
+    let text = text.replace('\t', "    ");
+    let mut fitted: String = text.chars().take(width).collect();
+    let length = fitted.chars().count();
+    fitted.extend(std::iter::repeat_n(' ', width - length));
+    fitted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "fn main() {\n    todo!()\n}\n";
+        let new = "fn main() {\n    println!(\"done\");\n}\n";
+        assert_eq!(
+            diff_lines(old, new),
+            vec![
+                Line::Equal("fn main() {"),
+                Line::Removed("    todo!()"),
+                Line::Added("    println!(\"done\");"),
+                Line::Equal("}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unified() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n";
+        let new = "1\n2\nthree\n4\n5\n6\n7\n8\n9\n";
+        assert_eq!(
+            unified(&diff_lines(old, new), 1),
+            vec![
+                "@@ -2,3 +2,3 @@",
+                " 2",
+                "-3",
+                "+three",
+                " 4",
+                "@@ -8,1 +8,2 @@",
+                " 8",
+                "+9",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fit() {
+        assert_eq!(fit("abc", 5), "abc  ");
+        assert_eq!(fit("abcdef", 4), "abcd");
+    }
+}
@@ -0,0 +1,120 @@
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// The ways loading, compiling or running the exercises can fail.
+// The I/O errors are shared so that the outputs carrying them can be cloned
+#[derive(Clone, Debug)]
+pub enum Error {
+    // The exercise list could not be parsed.
+    // The line and column start at 1, and are known for syntax errors only
+    Config {
+        path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+    // A file could not be read, most likely because it doesn't exist
+    MissingFile {
+        path: PathBuf,
+        source: Arc<io::Error>,
+    },
+    // A program of the Rust toolchain, such as `rustc`, cannot be found
+    ToolchainMissing {
+        program: String,
+    },
+    // A program, such as the compiler or the binary of an exercise, could not be started
+    Spawn {
+        program: String,
+        source: Arc<io::Error>,
+    },
+    // An exercise was stopped after running for the given number of seconds
+    Timeout {
+        exercise: String,
+        secs: u64,
+    },
+    // An exercise doesn't compile, fails, or is still marked as not done
+    NotDone {
+        exercise: String,
+    },
+    // The compiler has no explanation for the given error code
+    NoExplanation {
+        code: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    // The error of a file which could not be read
+    pub fn missing_file(path: impl Into<PathBuf>, source: io::Error) -> Error {
+        Error::MissingFile {
+            path: path.into(),
+            source: Arc::new(source),
+        }
+    }
+
+    // The error of a program which could not be started
+    pub fn spawn(program: impl Into<String>, source: io::Error) -> Error {
+        Error::Spawn {
+            program: program.into(),
+            source: Arc::new(source),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Config {
+                path,
+                line: Some(line),
+                column: Some(column),
+                message,
+            } => write!(f, "{}:{}:{}: {}", path.display(), line, column, message),
+            Error::Config { path, message, .. } => write!(f, "{}: {}", path.display(), message),
+            Error::MissingFile { path, source } => {
+                write!(f, "{} cannot be read: {}", path.display(), source)
+            }
+            Error::ToolchainMissing { program } => write!(f, "`{}` cannot be found", program),
+            Error::Spawn { program, source } => {
+                write!(f, "Could not run `{}`: {}", program, source)
+            }
+            Error::Timeout { exercise, secs } => {
+                write!(f, "{} was stopped after {} seconds", exercise, secs)
+            }
+            Error::NotDone { exercise } => write!(f, "{} is not done yet", exercise),
+            Error::NoExplanation { code } => write!(f, "There is no explanation for {}", code),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::MissingFile { source, .. } | Error::Spawn { source, .. } => Some(&**source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = Error::Config {
+            path: PathBuf::from("info.toml"),
+            line: Some(3),
+            column: Some(8),
+            message: String::from("expected a value"),
+        };
+        assert_eq!(error.to_string(), "info.toml:3:8: expected a value");
+        let error = Error::spawn("rustc", io::Error::from(io::ErrorKind::NotFound));
+        assert!(error.to_string().starts_with("Could not run `rustc`: "));
+        assert!(error::Error::source(&error).is_some());
+    }
+}
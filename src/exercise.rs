@@ -1,17 +1,76 @@
+use crate::comparisons::{self, Bound, ComparisonReport};
+use crate::complexity::{self, parse_measurements, Class, ComplexityReport, Input};
+use crate::diagnostic::{parse_diagnostics, Diagnostic};
+use crate::error::Error;
+use crate::prerequisites::check_prerequisites;
+use crate::progress::Progress;
+use crate::property::{self, Property};
+use crate::scratch::scratch_dir;
+use crate::stability;
+use crate::support::{support_args, SUPPORT_LIB};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use std::fs::{self, remove_file, File};
-use std::io::Read;
-use std::path::PathBuf;
-use std::process::{self, Command};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
 
 const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
+const RUSTC_JSON_ARGS: &[&str] = &["--error-format=json", "--json=diagnostic-rendered-ansi"];
+// The Rust edition the exercises are compiled with
+pub const EDITION: &str = "2018";
 const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
 const CONTEXT: usize = 2;
-const CLIPPY_CARGO_TOML_PATH: &str = "./rust-cs-fundamentals/clippy/Cargo.toml";
+// The directory of the scratch directory the Cargo projects linted with clippy are generated in
+const CLIPPY_DIR: &str = "clippy";
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_MEMORY_LIMIT_MB: u64 = 512;
+const WATCHDOG_INTERVAL: Duration = Duration::from_millis(10);
 
-// Get a temporary file name that is hopefully unique
+// Whether the given source code contains the `I AM NOT DONE` marker
+pub fn has_marker(source: &str) -> bool {
+    Regex::new(I_AM_DONE_REGEX).unwrap().is_match(source)
+}
+
+// Check that the rust compiler the exercises are compiled with can be run
+pub fn check_toolchain() -> Result<(), Error> {
+    let found = Command::new("rustc")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if found {
+        Ok(())
+    } else {
+        Err(Error::ToolchainMissing {
+            program: String::from("rustc"),
+        })
+    }
+}
+
+// The version of the rust compiler the exercises are compiled with
+pub fn toolchain_version() -> String {
+    static VERSION: OnceLock<String> = OnceLock::new();
+    VERSION
+        .get_or_init(|| {
+            Command::new("rustc")
+                .arg("--version")
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+                .unwrap_or_default()
+        })
+        .clone()
+}
+
+// The file the current thread compiles exercises to,
+// in the scratch directory of the run
 #[inline]
 fn temp_file() -> String {
     let thread_id: String = format!("{:?}", std::thread::current().id())
@@ -19,29 +78,196 @@ fn temp_file() -> String {
         .filter(|c| c.is_alphanumeric())
         .collect();
 
-    format!("./temp_{}_{}", process::id(), thread_id)
+    scratch_dir()
+        .join(format!("temp_{}", thread_id))
+        .to_string_lossy()
+        .into_owned()
 }
 
 // The mode of the exercise.
-#[derive(Deserialize, Copy, Clone)]
+#[derive(Deserialize, Serialize, Copy, Clone, PartialEq, Debug, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     // Indicates that the exercise should be compiled as a binary
+    #[default]
     Compile,
     // Indicates that the exercise should be compiled as a test harness
     Test,
     // Indicates that the exercise should be linted with clippy
     Clippy,
+    // Indicates that the exercise should be compiled as a binary
+    // which prints the expected output
+    Output,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mode = match self {
+            Mode::Compile => "compile",
+            Mode::Test => "test",
+            Mode::Clippy => "clippy",
+            Mode::Output => "output",
+        };
+        f.pad(mode)
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Mode, String> {
+        match s {
+            "compile" => Ok(Mode::Compile),
+            "test" => Ok(Mode::Test),
+            "clippy" => Ok(Mode::Clippy),
+            "output" => Ok(Mode::Output),
+            _ => Err(format!("unknown mode: {}", s)),
+        }
+    }
 }
 
 #[derive(Deserialize)]
 pub struct ExerciseList {
+    // The title of the track this is the manifest of
+    pub title: Option<String>,
+    // The directory holding the exercises, such as `fundamentals`.
+    // If it is declared, every exercise source below it has to be listed
+    pub root: Option<PathBuf>,
+    // The sections the exercises are grouped in, in the recommended order
+    #[serde(default)]
+    pub sections: Vec<Section>,
     pub exercises: Vec<Exercise>,
 }
 
+// A group of exercises on the same subject, such as the sorts
+#[derive(Deserialize, Clone, Debug)]
+pub struct Section {
+    // The name the exercises refer to the section by, such as `sorts`
+    pub name: String,
+    // The title of the section shown to the end user, such as `Sorting algorithms`
+    pub title: Option<String>,
+}
+
+impl Section {
+    pub fn title(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.name)
+    }
+}
+
+// How hard an exercise is
+#[derive(Deserialize, Serialize, Copy, Clone, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let difficulty = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        };
+        f.pad(difficulty)
+    }
+}
+
+// The part of the exercises a command works on: the exercises of a section,
+// those with a tag, or both. An empty scope holds every exercise
+#[derive(Default, Clone, Copy)]
+pub struct Scope<'a> {
+    pub section: Option<&'a str>,
+    pub tag: Option<&'a str>,
+}
+
+impl<'a> Scope<'a> {
+    pub fn contains(&self, exercise: &Exercise) -> bool {
+        self.section
+            .is_none_or(|section| exercise.section.as_deref() == Some(section))
+            && self
+                .tag
+                .is_none_or(|tag| exercise.tags.iter().any(|t| t == tag))
+    }
+}
+
+impl ExerciseList {
+    // The section with the given name, if it is declared
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    // The directory watched for changes to the exercises: the declared root,
+    // or else the deepest directory holding every exercise
+    pub fn watch_root(&self) -> PathBuf {
+        if let Some(root) = &self.root {
+            return root.clone();
+        }
+        let mut dirs = self
+            .exercises
+            .iter()
+            .map(|e| e.path.parent().unwrap_or_else(|| Path::new("")));
+        let first = dirs.next().unwrap_or_else(|| Path::new(""));
+        let common = dirs.fold(first, |common, dir| {
+            common
+                .ancestors()
+                .find(|ancestor| dir.starts_with(ancestor))
+                .unwrap_or_else(|| Path::new(""))
+        });
+        if common.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            common.to_path_buf()
+        }
+    }
+
+    // The exercises in the given scope, in the recommended order
+    pub fn scoped(&self, scope: Scope) -> Vec<Exercise> {
+        self.exercises
+            .iter()
+            .filter(|exercise| scope.contains(exercise))
+            .cloned()
+            .collect()
+    }
+
+    // Load the exercise list from the given file, such as info.toml.
+    // The prerequisites of the exercises must not form a cycle
+    pub fn load(path: impl AsRef<Path>) -> Result<ExerciseList, Error> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| Error::missing_file(path, e))?;
+        let list: ExerciseList = toml::from_str(&content).map_err(|e: toml::de::Error| {
+            let mut message = e.to_string();
+            // toml counts from 0, and appends the line to the message itself
+            let (line, column) = match e.line_col() {
+                Some((line, column)) => {
+                    if let Some(at) = message.rfind(" at line ") {
+                        message.truncate(at);
+                    }
+                    (Some(line + 1), Some(column + 1))
+                }
+                None => (None, None),
+            };
+            Error::Config {
+                path: path.to_path_buf(),
+                line,
+                column,
+                message,
+            }
+        })?;
+        check_prerequisites(&list.exercises).map_err(|message| Error::Config {
+            path: path.to_path_buf(),
+            line: None,
+            column: None,
+            message,
+        })?;
+        Ok(list)
+    }
+}
+
 // A representation of an exercise.
 // This is deserialized from the accompanying info.toml file
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone, Default)]
 pub struct Exercise {
     // Name of the exercise
     pub name: String,
@@ -49,8 +275,100 @@ pub struct Exercise {
     pub path: PathBuf,
     // The mode of the exercise (Test, Compile, or Clippy)
     pub mode: Mode,
-    // The hint text associated with the exercise
-    pub hint: String,
+    // The hints associated with the exercise, from a nudge to a near solution
+    #[serde(default)]
+    pub hint: Hint,
+    // The path to a markdown file containing the hints, one `## ` section per level
+    pub hints_file: Option<PathBuf>,
+    // The number of failed attempts needed to unlock each hint after the first one
+    pub unlock_hints_after: Option<u32>,
+    // The path to the file containing the reference solution, if any
+    pub solution: Option<PathBuf>,
+    // The number of seconds the exercise may run before being stopped
+    pub timeout_secs: Option<u64>,
+    // The number of megabytes of memory the exercise may allocate (Linux only)
+    pub memory_limit_mb: Option<u64>,
+    // The output an exercise in output mode has to print
+    pub expected_output: Option<String>,
+    // The path to the file containing the expected output, instead of expected_output
+    pub expected_output_file: Option<PathBuf>,
+    // How whitespace is normalized before comparing the output
+    #[serde(default)]
+    pub whitespace: Whitespace,
+    // The path to the file fed into the standard input of the binary
+    pub stdin_file: Option<PathBuf>,
+    // The path to a directory of `NN.in` inputs and `NN.out` expected outputs.
+    // The binary is run once per case instead of using stdin_file and expected_output
+    pub cases: Option<PathBuf>,
+    // The complexity class the running time of the exercise must not exceed, e.g. `n log n`
+    pub complexity: Option<Class>,
+    // The expression the harnesses check, measure or count the comparisons of,
+    // evaluated with a vector of numbers named `input` and, for searches,
+    // an `item` to look for, e.g. `merge_sort(&mut input)`
+    pub call: Option<String>,
+    // Whether the numbers of `input` are random or sorted when measuring the running time
+    #[serde(default)]
+    pub complexity_input: Input,
+    // The number of comparisons the exercise may make, e.g. `2 n log n`
+    pub comparisons: Option<Bound>,
+    // Whether `call` has to sort records with equal keys in their original order
+    #[serde(default)]
+    pub stable: bool,
+    // What `call` is checked against on thousands of generated inputs
+    pub property: Option<Property>,
+    // The name of the section the exercise belongs to, declared in `[[sections]]`
+    pub section: Option<String>,
+    // The subjects the exercise is about, such as `recursion`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // How hard the exercise is
+    pub difficulty: Option<Difficulty>,
+    // About how many minutes the exercise takes
+    pub estimated_minutes: Option<u32>,
+    // The names of the exercises which should be done before this one
+    #[serde(default)]
+    pub requires: Vec<String>,
+}
+
+// How whitespace is normalized before comparing the output of an exercise
+// with the expected output
+#[derive(Deserialize, Copy, Clone, PartialEq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Whitespace {
+    // The output must match exactly
+    Exact,
+    // Trailing whitespace on each line and trailing empty lines are ignored
+    #[default]
+    Trailing,
+    // Any sequence of whitespace is considered a single separator
+    Tokens,
+}
+
+impl Whitespace {
+    pub fn normalize(self, text: &str) -> String {
+        match self {
+            Whitespace::Exact => text.to_string(),
+            Whitespace::Trailing => {
+                let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+                lines.join("\n").trim_end().to_string()
+            }
+            Whitespace::Tokens => text.split_whitespace().collect::<Vec<_>>().join(" "),
+        }
+    }
+}
+
+// The hints of an exercise, either a single text or a list of levels
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+#[serde(untagged)]
+pub enum Hint {
+    Single(String),
+    Levels(Vec<String>),
+}
+
+impl Default for Hint {
+    fn default() -> Hint {
+        Hint::Single(String::new())
+    }
 }
 
 // An enum to track of the state of an Exercise.
@@ -59,7 +377,8 @@ pub struct Exercise {
 pub enum State {
     // The state of the exercise once it's been completed
     Done,
-    // The state of the exercise while it's not completed yet
+    // The state of the exercise while it's not completed yet.
+    // The context is empty when the exercise has simply never passed
     Pending(Vec<ContextLine>),
 }
 
@@ -87,13 +406,111 @@ impl<'a> CompiledExercise<'a> {
     }
 }
 
+impl ExerciseOutput {
+    // The output of an exercise which couldn't be run at all
+    pub fn error(message: String) -> ExerciseOutput {
+        ExerciseOutput {
+            stderr: message,
+            ..ExerciseOutput::default()
+        }
+    }
+
+    // The output of an exercise whose compiler or binary could not be started
+    pub fn spawn_failure(program: &str, source: io::Error) -> ExerciseOutput {
+        let error = Error::spawn(program, source);
+        ExerciseOutput {
+            stderr: error.to_string(),
+            error: Some(error),
+            ..ExerciseOutput::default()
+        }
+    }
+}
+
+// The verdict of a single case of an exercise with a cases directory
+#[derive(Serialize, Copy, Clone, PartialEq, Debug)]
+pub enum Verdict {
+    // The binary printed the expected output
+    #[serde(rename = "AC")]
+    Accepted,
+    // The binary printed something else
+    #[serde(rename = "WA")]
+    WrongAnswer,
+    // The binary was stopped after the time limit
+    #[serde(rename = "TLE")]
+    TimeLimitExceeded,
+    // The binary failed, or was stopped for using too much memory
+    #[serde(rename = "RE")]
+    RuntimeError,
+}
+
+impl Verdict {
+    // The verdict of a case whose binary didn't succeed
+    fn of(output: &ExerciseOutput) -> Verdict {
+        match output.exceeded {
+            Some(Limit::Time(_)) => Verdict::TimeLimitExceeded,
+            _ if output.expected.is_some() => Verdict::WrongAnswer,
+            _ => Verdict::RuntimeError,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(match self {
+            Verdict::Accepted => "AC",
+            Verdict::WrongAnswer => "WA",
+            Verdict::TimeLimitExceeded => "TLE",
+            Verdict::RuntimeError => "RE",
+        })
+    }
+}
+
+// The outcome of a single case of an exercise with a cases directory
+#[derive(Serialize, Clone, Debug)]
+pub struct CaseResult {
+    // The name of the case, the file name of its input without the extension
+    pub name: String,
+    pub verdict: Verdict,
+    // How long the binary ran, in seconds
+    pub duration_secs: f64,
+}
+
+// A resource limit enforced while running an exercise
+#[derive(Serialize, Debug, PartialEq, Copy, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum Limit {
+    // The binary ran for longer than the given number of seconds
+    Time(u64),
+    // The binary tried to allocate more than the given number of megabytes
+    Memory(u64),
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Limit::Time(secs) => write!(f, "timed out after {} seconds", secs),
+            Limit::Memory(mb) => write!(f, "exceeded the memory limit of {} MB", mb),
+        }
+    }
+}
+
 // A representation of an already executed binary
-#[derive(Debug)]
+#[derive(Debug, Default, Clone)]
 pub struct ExerciseOutput {
     // The textual contents of the standard output of the binary
     pub stdout: String,
     // The textual contents of the standard error of the binary
     pub stderr: String,
+    // The limit the binary exceeded, if it was stopped because of one
+    pub exceeded: Option<Limit>,
+    // The normalized output the binary should have printed, if it printed something else
+    pub expected: Option<String>,
+    // The diagnostics of the compiler, if this is the output of a compilation
+    pub diagnostics: Box<[Diagnostic]>,
+    // The outcome of every case, if the exercise has a cases directory
+    pub cases: Box<[CaseResult]>,
+    // The error which kept the exercise from being compiled or run at all, if any
+    pub error: Option<Error>,
 }
 
 struct FileHandle;
@@ -105,108 +522,386 @@ impl Drop for FileHandle {
 }
 
 impl Exercise {
-    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
+    // Compile the exercise, linked with the support library if there is one
+    pub fn compile(&self) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        let support = support_args()?;
+        let program = match self.mode {
+            Mode::Clippy => "cargo",
+            _ => "rustc",
+        };
         let cmd = match self.mode {
-            Mode::Compile => Command::new("rustc")
-                .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+            Mode::Compile | Mode::Output => Command::new("rustc")
+                .args([self.path.to_str().unwrap(), "-o", &temp_file()])
+                .args(["--edition", EDITION])
+                .args(RUSTC_JSON_ARGS)
+                .args(&support)
                 .output(),
             Mode::Test => Command::new("rustc")
-                .args(&["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+                .args(["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
+                .args(["--edition", EDITION])
+                .args(RUSTC_JSON_ARGS)
+                .args(&support)
                 .output(),
             Mode::Clippy => {
-                let cargo_toml = format!(
-                    r#"[package]
-name = "{}"
-version = "0.0.1"
-edition = "2018"
-[[bin]]
-name = "{}"
-path = "{}.rs""#,
-                    self.name, self.name, self.name
-                );
-                fs::write(CLIPPY_CARGO_TOML_PATH, cargo_toml)
-                    .expect("Failed to write 📎 Clippy 📎 Cargo.toml file.");
+                let manifest = match self.clippy_project() {
+                    Ok(manifest) => manifest,
+                    Err(e) => {
+                        return Err(ExerciseOutput::error(format!(
+                            "Could not generate the Cargo project of {}: {}",
+                            self, e
+                        )))
+                    }
+                };
                 // To support the ability to run the clipy exercises, build
                 // an executable, in addition to running clippy. With a
                 // compilation failure, this would silently fail. But we expect
                 // clippy to reflect the same failure while compiling later.
                 Command::new("rustc")
-                    .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
+                    .args([self.path.to_str().unwrap(), "-o", &temp_file()])
+                    .args(["--edition", EDITION])
                     .args(RUSTC_COLOR_ARGS)
+                    .args(&support)
                     .output()
-                    .expect("Failed to compile!");
-                // Due to an issue with Clippy, a cargo clean is required to catch all lints.
-                // See https://github.com/rust-lang/rust-clippy/issues/2604
-                // This is already fixed on Clippy's master branch. See this issue to track merging into Cargo:
-                // https://github.com/rust-lang/rust-clippy/issues/3837
+                    .map_err(|e| ExerciseOutput::spawn_failure("rustc", e))?;
                 Command::new("cargo")
-                    .args(&["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .output()
-                    .expect("Failed to run 'cargo clean'");
-                Command::new("cargo")
-                    .args(&["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .args(&["--", "-D", "warnings"])
+                    .arg("clippy")
+                    .arg("--manifest-path")
+                    .arg(&manifest)
+                    .args(["--message-format", "json-diagnostic-rendered-ansi"])
+                    .args(["--", "-D", "warnings"])
                     .output()
             }
         }
-        .expect("Failed to run 'compile' command.");
+        .map_err(|e| ExerciseOutput::spawn_failure(program, e))?;
 
         if cmd.status.success() {
             Ok(CompiledExercise {
-                exercise: &self,
+                exercise: self,
                 _handle: FileHandle,
             })
         } else {
             clean();
-            Err(ExerciseOutput {
-                stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-                stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            Err(compilation_failure(&cmd))
+        }
+    }
+
+    // Generate the throwaway Cargo project clippy lints the exercise in, in the
+    // scratch directory of the run, and return the path of its manifest.
+    // The support library becomes a dependency of its own if there is one
+    fn clippy_project(&self) -> io::Result<PathBuf> {
+        let dir = scratch_dir().join(CLIPPY_DIR);
+        let mut dependencies = String::new();
+        if Path::new(SUPPORT_LIB).exists() {
+            let support = dir.join("support");
+            fs::create_dir_all(&support)?;
+            fs::write(
+                support.join("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"support\"\nversion = \"0.0.1\"\nedition = \"{}\"\n\n\
+                     [lib]\npath = {:?}\n\n[workspace]\n",
+                    EDITION,
+                    fs::canonicalize(SUPPORT_LIB)?
+                ),
+            )?;
+            dependencies = format!("support = {{ path = {:?} }}\n", fs::canonicalize(&support)?);
+        }
+
+        let project = dir.join(&self.name);
+        fs::create_dir_all(&project)?;
+        let manifest = project.join("Cargo.toml");
+        fs::write(
+            &manifest,
+            format!(
+                "[package]\nname = {name:?}\nversion = \"0.0.1\"\nedition = \"{edition}\"\n\n\
+                 [[bin]]\nname = {name:?}\npath = {path:?}\n\n\
+                 [dependencies]\n{dependencies}\n[workspace]\n",
+                name = self.name,
+                edition = EDITION,
+                path = fs::canonicalize(&self.path)?,
+                dependencies = dependencies,
+            ),
+        )?;
+        Ok(manifest)
+    }
+
+    // Measure how the running time of the exercise grows with the size of its input,
+    // if it declares a complexity class
+    pub fn measure_complexity(&self) -> Option<Result<ComplexityReport, ExerciseOutput>> {
+        let declared = self.complexity?;
+        let harness = complexity::harness(self.call.as_deref()?, self.complexity_input);
+        let output = self.run_harness(&harness, complexity::HARNESS_TEST, "complexity");
+        Some(output.and_then(|output| {
+            ComplexityReport::new(declared, parse_measurements(&output.stdout)).ok_or_else(|| {
+                ExerciseOutput {
+                    stderr: String::from("Too few input sizes could be measured in time"),
+                    ..output
+                }
+            })
+        }))
+    }
+
+    // Count the comparisons the exercise makes on random and sorted inputs,
+    // if it declares a bound on them
+    pub fn count_comparisons(&self) -> Option<Result<ComparisonReport, ExerciseOutput>> {
+        let bound = self.comparisons?;
+        let harness = comparisons::harness(self.call.as_deref()?);
+        let output = self.run_harness(&harness, comparisons::HARNESS_TEST, "comparisons");
+        Some(output.and_then(|output| {
+            ComparisonReport::new(bound, &output.stdout).ok_or_else(|| ExerciseOutput {
+                stderr: String::from("The comparisons could not be counted"),
+                ..output
             })
+        }))
+    }
+
+    // Check the exercise against a reference implementation on generated inputs,
+    // if it declares a property. The output is the one of the failing harness otherwise
+    pub fn check_property(&self) -> Option<Result<ExerciseOutput, ExerciseOutput>> {
+        let harness = property::harness(self.call.as_deref()?, self.property?);
+        Some(self.run_harness(&harness, property::HARNESS_TEST, "property"))
+    }
+
+    // Check that the exercise sorts records with equal keys stably,
+    // if it has to. The output is the one of the failing harness otherwise
+    pub fn check_stability(&self) -> Option<Result<ExerciseOutput, ExerciseOutput>> {
+        if !self.stable {
+            return None;
+        }
+        let harness = stability::harness(self.call.as_deref()?);
+        Some(self.run_harness(&harness, stability::HARNESS_TEST, "stability"))
+    }
+
+    // Append the given test harness to the source of the exercise,
+    // compile it with optimizations and run the given test of it
+    pub fn run_harness(
+        &self,
+        harness: &str,
+        test: &str,
+        suffix: &str,
+    ) -> Result<ExerciseOutput, ExerciseOutput> {
+        let source = fs::read_to_string(&self.path)
+            .map_err(|e| ExerciseOutput::error(format!("Could not read {}: {}", self, e)))?;
+        let binary = format!("{}_{}", temp_file(), suffix);
+        let harness_path = format!("{}.rs", binary);
+        fs::write(&harness_path, source + harness)
+            .map_err(|e| ExerciseOutput::error(format!("Could not write the harness: {}", e)))?;
+        let cmd = Command::new("rustc")
+            .args(["--test", "-O", &harness_path, "-o", &binary])
+            .args(["--edition", EDITION])
+            .args(RUSTC_JSON_ARGS)
+            .args(support_args()?)
+            .output()
+            .map_err(|e| ExerciseOutput::spawn_failure("rustc", e))?;
+        let _ignored = remove_file(&harness_path);
+        if !cmd.status.success() {
+            return Err(compilation_failure(&cmd));
         }
+
+        let args = [test, "--exact", "--nocapture"];
+        let output = self.spawn_binary(&binary, &args, Stdio::null(), None);
+        let _ignored = remove_file(&binary);
+        output
     }
 
+    // Run the compiled binary, once per case if the exercise has a cases directory
     fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        let arg = match self.mode {
-            Mode::Test => "--show-output",
-            _ => "",
+        if let Some(cases) = self.cases.as_ref().filter(|_| self.mode == Mode::Output) {
+            return self.run_cases(cases);
+        }
+
+        let expected = match self.expected_output() {
+            Ok(expected) => expected,
+            Err(e) => {
+                return Err(ExerciseOutput::error(format!(
+                    "Could not read the expected output: {}",
+                    e
+                )))
+            }
         };
-        let cmd = Command::new(&temp_file())
-            .arg(arg)
-            .output()
-            .expect("Failed to run 'run' command");
+        let stdin = match &self.stdin_file {
+            Some(stdin_file) => match File::open(stdin_file) {
+                Ok(file) => Stdio::from(file),
+                Err(e) => {
+                    return Err(ExerciseOutput::error(format!(
+                        "Could not open {}: {}",
+                        stdin_file.display(),
+                        e
+                    )))
+                }
+            },
+            None => Stdio::null(),
+        };
+        self.run_binary(stdin, expected)
+    }
+
+    // Run the binary once for every case of the given directory,
+    // feeding it `NN.in` and comparing its output with `NN.out`.
+    // Every case is run, the output is the one of the first failing case
+    fn run_cases(&self, dir: &Path) -> Result<ExerciseOutput, ExerciseOutput> {
+        let inputs = match case_inputs(dir) {
+            Ok(inputs) if !inputs.is_empty() => inputs,
+            Ok(_) => {
+                return Err(ExerciseOutput::error(format!(
+                    "There is no case in {}",
+                    dir.display()
+                )))
+            }
+            Err(e) => {
+                return Err(ExerciseOutput::error(format!(
+                    "Could not read {}: {}",
+                    dir.display(),
+                    e
+                )))
+            }
+        };
+
+        let mut cases = Vec::new();
+        let mut first_failure = None;
+        for input in inputs {
+            let expected_path = input.with_extension("out");
+            let (stdin, expected) = match (File::open(&input), fs::read_to_string(&expected_path)) {
+                (Ok(stdin), Ok(expected)) => (stdin, expected),
+                (Err(e), _) | (_, Err(e)) => {
+                    return Err(ExerciseOutput::error(format!(
+                        "Could not read the case {}: {}",
+                        input.display(),
+                        e
+                    )))
+                }
+            };
+
+            let start = Instant::now();
+            let outcome = self.run_binary(Stdio::from(stdin), Some(expected));
+            let duration = start.elapsed();
+            let (verdict, output) = match outcome {
+                Ok(output) => (Verdict::Accepted, output),
+                Err(output) => (Verdict::of(&output), output),
+            };
+            cases.push(CaseResult {
+                name: input.file_stem().unwrap().to_string_lossy().to_string(),
+                verdict,
+                duration_secs: duration.as_secs_f64(),
+            });
+            if verdict != Verdict::Accepted && first_failure.is_none() {
+                first_failure = Some(output);
+            }
+        }
+
+        match first_failure {
+            Some(output) => Err(ExerciseOutput {
+                cases: cases.into(),
+                ..output
+            }),
+            None => Ok(ExerciseOutput {
+                cases: cases.into(),
+                ..ExerciseOutput::default()
+            }),
+        }
+    }
+
+    // Run the compiled binary within the time and memory limits of the exercise.
+    // A watchdog kills the binary once its deadline has passed.
+    // If an output is expected, the binary only succeeds if it prints it
+    fn run_binary(
+        &self,
+        stdin: Stdio,
+        expected: Option<String>,
+    ) -> Result<ExerciseOutput, ExerciseOutput> {
+        let args: &[&str] = match self.mode {
+            Mode::Test => &["--show-output"],
+            _ => &[],
+        };
+        self.spawn_binary(&temp_file(), args, stdin, expected)
+    }
+
+    // Run the given binary within the time and memory limits of the exercise
+    fn spawn_binary(
+        &self,
+        binary: &str,
+        args: &[&str],
+        stdin: Stdio,
+        expected: Option<String>,
+    ) -> Result<ExerciseOutput, ExerciseOutput> {
+        let timeout_secs = self.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
+        let memory_limit_mb = self.memory_limit_mb.unwrap_or(DEFAULT_MEMORY_LIMIT_MB);
+
+        let mut command = Command::new(binary);
+        command
+            .args(args)
+            .stdin(stdin)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        limit_memory(&mut command, memory_limit_mb);
+        let mut child = command
+            .spawn()
+            .map_err(|e| ExerciseOutput::spawn_failure(binary, e))?;
+
+        let stdout = read_in_background(child.stdout.take());
+        let stderr = read_in_background(child.stderr.take());
+
+        let deadline = Instant::now() + Duration::from_secs(timeout_secs);
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Err(e) => {
+                    let _ = child.kill();
+                    return Err(ExerciseOutput::spawn_failure(binary, e));
+                }
+                Ok(None) if Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    break None;
+                }
+                Ok(None) => thread::sleep(WATCHDOG_INTERVAL),
+            }
+        };
+
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        let exceeded = match status {
+            None => Some(Limit::Time(timeout_secs)),
+            Some(status) if !status.success() && is_out_of_memory(&stderr) => {
+                Some(Limit::Memory(memory_limit_mb))
+            }
+            Some(_) => None,
+        };
+        let mut success = exceeded.is_none() && status.is_some_and(|status| status.success());
+        let expected = expected
+            .map(|expected| self.whitespace.normalize(&expected))
+            .filter(|expected| success && *expected != self.whitespace.normalize(&stdout));
+        success &= expected.is_none();
 
         let output = ExerciseOutput {
-            stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            stdout,
+            stderr,
+            exceeded,
+            expected,
+            ..ExerciseOutput::default()
         };
 
-        if cmd.status.success() {
+        if success {
             Ok(output)
         } else {
             Err(output)
         }
     }
 
-    pub fn state(&self) -> State {
-        let mut source_file =
-            File::open(&self.path).expect("We were unable to open the exercise file!");
-
-        let source = {
-            let mut s = String::new();
-            source_file
-                .read_to_string(&mut s)
-                .expect("We were unable to read the exercise file!");
-            s
-        };
+    // The state of the exercise is driven by the recorded progress.
+    // An exercise is done once it has passed, unless the `I AM NOT DONE`
+    // marker is still present to signal that the learner keeps working on it.
+    // Fails if the exercise file cannot be read
+    pub fn state(&self, progress: &Progress) -> Result<State, Error> {
+        let source =
+            fs::read_to_string(&self.path).map_err(|e| Error::missing_file(&self.path, e))?;
 
         let re = Regex::new(I_AM_DONE_REGEX).unwrap();
 
         if !re.is_match(&source) {
-            return State::Done;
+            return Ok(if progress.is_solved(self) {
+                State::Done
+            } else {
+                State::Pending(Vec::new())
+            });
         }
 
         let matched_line_index = source
@@ -230,19 +925,188 @@ path = "{}.rs""#,
             })
             .collect();
 
-        State::Pending(context)
+        Ok(State::Pending(context))
+    }
+
+    // Whether the exercise is done. An exercise which cannot be read is not
+    pub fn is_done(&self, progress: &Progress) -> bool {
+        matches!(self.state(progress), Ok(State::Done))
+    }
+
+    // Hash the source code of the exercise, so that a recorded result
+    // can be matched against the code that produced it.
+    // The input and expected output of the exercise are part of the hash as well,
+    // along with how it is run and checked, and the checks of the harnesses
+    pub fn source_hash(&self) -> io::Result<String> {
+        let mut source = fs::read(&self.path)?;
+        let settings = format!(
+            "{:?} {:?} {:?} {:?}",
+            self.mode, self.timeout_secs, self.memory_limit_mb, self.whitespace
+        );
+        source.extend(settings.as_bytes());
+        for file in [&self.stdin_file, &self.expected_output_file]
+            .iter()
+            .copied()
+            .flatten()
+        {
+            source.extend(fs::read(file)?);
+        }
+        if let Some(cases) = &self.cases {
+            for input in case_inputs(cases)? {
+                source.extend(fs::read(&input)?);
+                source.extend(fs::read(input.with_extension("out"))?);
+            }
+        }
+        if let Some(expected_output) = &self.expected_output {
+            source.extend(expected_output.as_bytes());
+        }
+        if let Some(call) = &self.call {
+            let checks = format!(
+                "{} {:?} {:?} {:?} {} {:?}",
+                call,
+                self.complexity,
+                self.complexity_input,
+                self.comparisons,
+                self.stable,
+                self.property
+            );
+            source.extend(checks.as_bytes());
+        }
+        Ok(format!("{:016x}", fnv1a(&source)))
+    }
+
+    // The output the exercise has to print, if it is in output mode
+    pub fn expected_output(&self) -> io::Result<Option<String>> {
+        if self.mode != Mode::Output {
+            return Ok(None);
+        }
+        match (&self.expected_output_file, &self.expected_output) {
+            (Some(expected_output_file), _) => fs::read_to_string(expected_output_file).map(Some),
+            (None, expected_output) => Ok(Some(expected_output.clone().unwrap_or_default())),
+        }
+    }
+
+    // The levels of hints of the exercise, in the order they are revealed.
+    // They are read from the hints file if there is one
+    pub fn hints(&self) -> io::Result<Vec<String>> {
+        let levels = match (&self.hints_file, &self.hint) {
+            (Some(hints_file), _) => markdown_levels(&fs::read_to_string(hints_file)?),
+            (None, Hint::Single(hint)) => vec![hint.trim().to_string()],
+            (None, Hint::Levels(levels)) => levels.iter().map(|l| l.trim().to_string()).collect(),
+        };
+        Ok(levels)
+    }
+}
+
+// Split a markdown document into one level per `## ` section.
+// Anything before the first section, such as a title, is left out
+fn markdown_levels(markdown: &str) -> Vec<String> {
+    let mut levels: Vec<String> = Vec::new();
+    for line in markdown.lines() {
+        if line.starts_with("## ") {
+            levels.push(String::new());
+        }
+        if let Some(level) = levels.last_mut() {
+            level.push_str(line);
+            level.push('\n');
+        }
     }
+    levels
+        .iter()
+        .map(|level| level.trim().to_string())
+        .collect()
 }
 
 impl Display for Exercise {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}", self.path.to_str().unwrap())
+        write!(f, "{}", self.path.display())
+    }
+}
+
+// The output of a failed compilation, with the diagnostics of the compiler
+fn compilation_failure(cmd: &process::Output) -> ExerciseOutput {
+    let stdout = String::from_utf8_lossy(&cmd.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&cmd.stderr).to_string();
+    let (diagnostics, rendered) = parse_diagnostics(&format!("{}\n{}", stdout, stderr));
+    // Keep the raw output if the compiler couldn't even report diagnostics
+    let (stdout, stderr) = if diagnostics.is_empty() {
+        (stdout, stderr)
+    } else {
+        (String::new(), rendered)
+    };
+    ExerciseOutput {
+        stdout,
+        stderr,
+        diagnostics: diagnostics.into(),
+        ..ExerciseOutput::default()
     }
 }
 
+// The inputs of the cases in the given directory, in order
+pub fn case_inputs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut inputs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "in") {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+// Read the given output of a child process to the end on a separate thread,
+// so that a child filling its pipes cannot block the watchdog
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        String::from_utf8_lossy(&bytes).to_string()
+    })
+}
+
+// Limit the data segment of the spawned binary, which makes
+// any allocation beyond the limit fail
+#[cfg(target_os = "linux")]
+fn limit_memory(command: &mut Command, memory_limit_mb: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let bytes = memory_limit_mb.saturating_mul(1024 * 1024) as libc::rlim_t;
+    // SAFETY: setrlimit is async-signal-safe and only affects the child process
+    unsafe {
+        command.pre_exec(move || {
+            let limit = libc::rlimit {
+                rlim_cur: bytes,
+                rlim_max: bytes,
+            };
+            if libc::setrlimit(libc::RLIMIT_DATA, &limit) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn limit_memory(_command: &mut Command, _memory_limit_mb: u64) {}
+
+// Whether the standard error of a failed binary shows that an allocation failed
+fn is_out_of_memory(stderr: &str) -> bool {
+    stderr.contains("memory allocation of") && stderr.contains("failed")
+}
+
+// A 64-bit FNV-1a hash, stable across toolchains unlike `DefaultHasher`
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
 #[inline]
 fn clean() {
-    let _ignored = remove_file(&temp_file());
+    let _ignored = remove_file(temp_file());
 }
 
 #[cfg(test)]
@@ -250,14 +1114,103 @@ mod test {
     use super::*;
     use std::path::Path;
 
+    #[test]
+    fn test_scope() {
+        let list: ExerciseList = toml::from_str(
+            r#"
+            [[sections]]
+            name = "graphs"
+
+            [[exercises]]
+            name = "bfs"
+            path = "bfs.rs"
+            mode = "test"
+            section = "graphs"
+            tags = ["queues"]
+
+            [[exercises]]
+            name = "dfs"
+            path = "dfs.rs"
+            mode = "test"
+            section = "graphs"
+            "#,
+        )
+        .unwrap();
+        let names =
+            |scope| -> Vec<String> { list.scoped(scope).into_iter().map(|e| e.name).collect() };
+        assert_eq!(names(Scope::default()), ["bfs", "dfs"]);
+        let graphs = Scope {
+            section: Some("graphs"),
+            tag: None,
+        };
+        assert_eq!(names(graphs), ["bfs", "dfs"]);
+        let queues = Scope {
+            tag: Some("queues"),
+            ..graphs
+        };
+        assert_eq!(names(queues), ["bfs"]);
+        assert!(list.section("graphs").is_some());
+        assert!(list.section("sorts").is_none());
+    }
+
+    #[test]
+    fn test_watch_root() {
+        let list: ExerciseList = toml::from_str(
+            r#"
+            [[exercises]]
+            name = "heap_sort"
+            path = "fundamentals/algorithms/sorts/heap_sort.rs"
+            mode = "test"
+
+            [[exercises]]
+            name = "bfs"
+            path = "fundamentals/algorithms/searches/bfs.rs"
+            mode = "test"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(list.watch_root(), Path::new("fundamentals/algorithms"));
+        let list = ExerciseList {
+            root: Some(PathBuf::from("fundamentals")),
+            ..list
+        };
+        assert_eq!(list.watch_root(), Path::new("fundamentals"));
+        let list = ExerciseList {
+            root: None,
+            exercises: vec![list.exercises[0].clone(), list.exercises[0].clone()],
+            ..list
+        };
+        assert_eq!(
+            list.watch_root(),
+            Path::new("fundamentals/algorithms/sorts")
+        );
+    }
+
+    #[test]
+    fn test_normalize_whitespace() {
+        let output = "1 2  \n3\n\n";
+        assert_eq!(Whitespace::Exact.normalize(output), output);
+        assert_eq!(Whitespace::Trailing.normalize(output), "1 2\n3");
+        assert_eq!(Whitespace::Tokens.normalize(output), "1 2 3");
+    }
+
+    #[test]
+    fn test_markdown_levels() {
+        let markdown = "# merge_sort\n\n## Nudge\nSplit it.\n\n## Approach\nMerge the halves.\n";
+        assert_eq!(
+            markdown_levels(markdown),
+            vec!["## Nudge\nSplit it.", "## Approach\nMerge the halves."]
+        );
+    }
+
     #[test]
     fn test_clean() {
-        File::create(&temp_file()).unwrap();
+        File::create(temp_file()).unwrap();
         let exercise = Exercise {
             name: String::from("example"),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
             mode: Mode::Compile,
-            hint: String::from(""),
+            ..Default::default()
         };
         let compiled = exercise.compile().unwrap();
         drop(compiled);
@@ -270,10 +1223,10 @@ mod test {
             name: "pending_exercise".into(),
             path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
             mode: Mode::Compile,
-            hint: String::new(),
+            ..Default::default()
         };
 
-        let state = exercise.state();
+        let state = exercise.state(&Progress::new("unused.toml")).unwrap();
         let expected = vec![
             ContextLine {
                 line: "// fake_exercise".to_string(),
@@ -311,10 +1264,33 @@ mod test {
             name: "finished_exercise".into(),
             path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
             mode: Mode::Compile,
-            hint: String::new(),
+            ..Default::default()
         };
 
-        assert_eq!(exercise.state(), State::Done);
+        let mut progress = Progress::new("unused.toml");
+        assert_eq!(
+            exercise.state(&progress).unwrap(),
+            State::Pending(Vec::new())
+        );
+
+        progress.record(&exercise, true, exercise.source_hash().ok());
+        assert_eq!(exercise.state(&progress).unwrap(), State::Done);
+    }
+
+    #[test]
+    fn test_missing_exercise() {
+        let exercise = Exercise {
+            name: "missing_exercise".into(),
+            path: PathBuf::from("tests/fixture/state/missing_exercise.rs"),
+            mode: Mode::Compile,
+            ..Default::default()
+        };
+        let progress = Progress::new("unused.toml");
+        assert!(matches!(
+            exercise.state(&progress),
+            Err(Error::MissingFile { .. })
+        ));
+        assert!(!exercise.is_done(&progress));
     }
 
     #[test]
@@ -323,7 +1299,7 @@ mod test {
             name: "exercise_with_output".into(),
             path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
             mode: Mode::Test,
-            hint: String::new(),
+            ..Default::default()
         };
         let out = exercise.compile().unwrap().run().unwrap();
         assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
@@ -0,0 +1,36 @@
+// The generator of the inputs of the harnesses, see `harness/input.rs`
+pub const INPUT_SOURCE: &str = include_str!("../harness/input.rs");
+
+// The message a failing harness test panicked with,
+// without the rest of the output of the test harness
+pub fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr
+        .lines()
+        .skip_while(|line| !line.contains("panicked at"));
+    lines.next()?;
+    let message: Vec<&str> = lines
+        .take_while(|line| !line.starts_with("note: run with") && *line != "stack backtrace:")
+        .collect();
+    Some(message.join("\n").trim_end().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_panic_message() {
+        let stderr = "thread '__algo_stability::stable' panicked at ./temp_stability.rs:80:13:\n\
+                      The sort is not stable: of the records with key 1, the one at index 9 \
+                      now comes before the one at index 3\n\
+                      note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(
+            panic_message(stderr).as_deref(),
+            Some(
+                "The sort is not stable: of the records with key 1, \
+                 the one at index 9 now comes before the one at index 3"
+            )
+        );
+        assert_eq!(panic_message("test result: ok"), None);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::exercise::Exercise;
+use crate::progress::Progress;
+use console::style;
+
+// Show the hints of the given exercise, one more level at each call.
+// If all is set, every unlocked level is shown at once.
+// Levels after the first one may be locked until enough attempts
+// at the exercise have failed. Revealed levels are recorded in the progress
+pub fn hint(exercise: &Exercise, progress: &mut Progress, all: bool) -> Result<(), ()> {
+    let levels = match exercise.hints() {
+        Ok(levels) => levels,
+        Err(e) => {
+            warn!("Could not read the hints of {}", exercise);
+            println!("{}", e);
+            return Err(());
+        }
+    };
+    if levels.iter().all(|level| level.is_empty()) {
+        warn!("There is no hint for {}", exercise);
+        return Err(());
+    }
+
+    let failures = progress.get(exercise).map_or(0, |p| p.failures);
+    let unlocked = unlocked_levels(levels.len(), failures, exercise.unlock_hints_after);
+    let revealed = progress.get(exercise).map_or(0, |p| p.hints_revealed) as usize;
+    let revealed = if all {
+        unlocked
+    } else {
+        (revealed + 1).min(unlocked)
+    };
+
+    if levels.len() == 1 {
+        println!("{}", levels[0]);
+    } else {
+        let shown = if all { 0 } else { revealed - 1 };
+        for (i, level) in levels.iter().enumerate().take(revealed).skip(shown) {
+            println!(
+                "{}",
+                style(format!("Hint {} of {}:", i + 1, levels.len())).bold()
+            );
+            println!("{}", level);
+            println!();
+        }
+        print_next_level(exercise, revealed, levels.len(), failures);
+    }
+
+    progress.reveal_hints(exercise, revealed as u32);
+    if let Err(e) = progress.save() {
+        warn!("Could not save your progress: {}", e);
+    }
+    Ok(())
+}
+
+// Tell the end user how to get the level following the revealed ones
+fn print_next_level(exercise: &Exercise, revealed: usize, total: usize, failures: u32) {
+    if revealed == total {
+        println!("This was the last hint for {}.", exercise);
+        return;
+    }
+    match exercise.unlock_hints_after {
+        Some(after) if failures < after * revealed as u32 => println!(
+            "The next hint unlocks after {} more failed attempts.",
+            after * revealed as u32 - failures
+        ),
+        _ => println!("Run `algo hint {}` again for the next hint.", exercise.name),
+    }
+}
+
+// The number of levels available after the given number of failed attempts.
+// The first level is always available, and every following one needs
+// another unlock_after failures
+fn unlocked_levels(total: usize, failures: u32, unlock_after: Option<u32>) -> usize {
+    match unlock_after {
+        Some(after) if after > 0 => total.min(1 + (failures / after) as usize),
+        _ => total,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unlocked_levels() {
+        assert_eq!(unlocked_levels(3, 0, None), 3);
+        assert_eq!(unlocked_levels(3, 0, Some(2)), 1);
+        assert_eq!(unlocked_levels(3, 3, Some(2)), 2);
+        assert_eq!(unlocked_levels(3, 10, Some(2)), 3);
+    }
+}
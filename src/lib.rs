@@ -0,0 +1,62 @@
+// The exercise engine behind the `algo` command line: loading the exercise list,
+// compiling, running and verifying the exercises, and recording the progress.
+// The subcommands of `algo`, which report to the end user, are in `commands`
+
+// The subcommands report why they failed to the end user themselves,
+// they only return whether they succeeded. A failed compilation or run
+// returns its whole output, which is only built once per exercise
+#![allow(clippy::result_unit_err, clippy::result_large_err)]
+
+#[macro_use]
+mod ui;
+
+mod bench;
+mod check;
+mod comparisons;
+mod complexity;
+mod diagnostic;
+mod diff;
+mod error;
+mod exercise;
+mod harness;
+mod hint;
+mod list;
+mod lsp;
+mod prerequisites;
+mod progress;
+mod property;
+mod report;
+mod reset;
+mod run;
+pub mod scratch;
+mod solution;
+mod stability;
+mod support;
+mod track;
+mod verify;
+mod watch;
+
+pub use crate::error::{Error, Result};
+pub use crate::exercise::{
+    check_toolchain, Difficulty, Exercise, ExerciseList, Mode, Scope, Section, State,
+};
+pub use crate::progress::{Progress, PROGRESS_FILE_PATH};
+pub use crate::report::{ExerciseResult, Status};
+pub use crate::run::run;
+pub use crate::track::{Track, DEFAULT_TRACK, TRACKS_DIR};
+pub use crate::verify::verify;
+
+// The subcommands of `algo` besides `run` and `verify`
+pub mod commands {
+    pub use crate::bench::bench;
+    pub use crate::check::check_content;
+    pub use crate::diagnostic::explain;
+    pub use crate::hint::hint;
+    pub use crate::list::{list, Filter, Format};
+    pub use crate::lsp::{lsp, refresh_project_file, PROJECT_FILE_PATH};
+    pub use crate::report::{write_report, ReportFormat};
+    pub use crate::reset::reset;
+    pub use crate::solution::solution;
+    pub use crate::track::tracks;
+    pub use crate::watch::{watch, Schedule, WatchStatus};
+}
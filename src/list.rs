@@ -0,0 +1,265 @@
+use crate::exercise::{Difficulty, Exercise, Mode, Section, State};
+use crate::progress::Progress;
+use console::{style, StyledObject};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+// The output format of the exercise list
+#[derive(Copy, Clone)]
+pub enum Format {
+    // A human readable table
+    Table,
+    // A JSON document meant to be consumed by other tools
+    Json,
+    // A tree of the prerequisites, every exercise under those it requires
+    Tree,
+}
+
+// The criteria an exercise has to match in order to be listed
+pub struct Filter<'a> {
+    // Only list the exercises which are done
+    pub solved: bool,
+    // Only list the exercises which are still pending
+    pub pending: bool,
+    // Only list the exercises of the given mode
+    pub mode: Option<Mode>,
+    // Only list the exercises whose name or path contains the given text
+    pub text: Option<&'a str>,
+}
+
+impl<'a> Filter<'a> {
+    fn matches(&self, exercise: &Exercise, done: bool) -> bool {
+        if (self.solved && !done) || (self.pending && done) {
+            return false;
+        }
+        if self.mode.is_some_and(|mode| mode != exercise.mode) {
+            return false;
+        }
+        self.text.is_none_or(|text| {
+            exercise.name.contains(text) || exercise.path.to_string_lossy().contains(text)
+        })
+    }
+}
+
+// A single row of the exercise list
+#[derive(Serialize)]
+struct Entry<'a> {
+    name: &'a str,
+    path: &'a Path,
+    mode: Mode,
+    done: bool,
+    // Whether the exercise file cannot be read, for instance because it was moved
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    missing: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    section: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    difficulty: Option<Difficulty>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_minutes: Option<u32>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    requires: &'a [String],
+}
+
+// The summary of the listed exercises
+#[derive(Serialize)]
+struct Summary {
+    done: usize,
+    total: usize,
+    percentage: f64,
+}
+
+#[derive(Serialize)]
+struct Listing<'a> {
+    exercises: Vec<Entry<'a>>,
+    summary: Summary,
+}
+
+// Print the exercises matching the given filter along with their state,
+// under the title of their section if they belong to one.
+// The summary only accounts for the listed exercises
+pub fn list(
+    exercises: &[Exercise],
+    sections: &[Section],
+    progress: &Progress,
+    filter: &Filter,
+    format: Format,
+) {
+    let entries: Vec<Entry> = exercises
+        .iter()
+        .map(|exercise| (exercise, exercise.state(progress)))
+        .filter(|(exercise, state)| filter.matches(exercise, matches!(state, Ok(State::Done))))
+        .map(|(exercise, state)| Entry {
+            name: &exercise.name,
+            path: &exercise.path,
+            mode: exercise.mode,
+            done: matches!(state, Ok(State::Done)),
+            missing: state.is_err(),
+            section: exercise.section.as_deref(),
+            tags: &exercise.tags,
+            difficulty: exercise.difficulty,
+            estimated_minutes: exercise.estimated_minutes,
+            requires: &exercise.requires,
+        })
+        .collect();
+
+    let done = entries.iter().filter(|entry| entry.done).count();
+    let total = entries.len();
+    let percentage = if total == 0 {
+        0.0
+    } else {
+        done as f64 * 100.0 / total as f64
+    };
+    let listing = Listing {
+        exercises: entries,
+        summary: Summary {
+            done,
+            total,
+            percentage,
+        },
+    };
+
+    match format {
+        Format::Table => print_table(&listing, sections),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&listing).unwrap()),
+        Format::Tree => print_tree(&listing),
+    }
+}
+
+// Print every listed exercise under the listed exercises it requires.
+// An exercise with several prerequisites is shown under each of them,
+// but the exercises requiring it only under the first one
+fn print_tree(listing: &Listing) {
+    let entries = &listing.exercises;
+    let listed: HashSet<&str> = entries.iter().map(|entry| entry.name).collect();
+    let mut shown = HashSet::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if !entry.requires.iter().any(|r| listed.contains(r.as_str())) {
+            print_branch(entries, index, "", "", &mut shown);
+        }
+    }
+
+    let summary = &listing.summary;
+    println!();
+    println!("{}", completion(summary.done, summary.total));
+}
+
+// Print the exercise at the given index after the given lead,
+// then the exercises requiring it below, indented by the given indent
+fn print_branch(
+    entries: &[Entry],
+    index: usize,
+    lead: &str,
+    indent: &str,
+    shown: &mut HashSet<usize>,
+) {
+    let entry = &entries[index];
+    let state = styled_state(entry);
+    if !shown.insert(index) {
+        println!("{}{}  {} (see above)", lead, entry.name, state);
+        return;
+    }
+    println!("{}{}  {}", lead, entry.name, state);
+
+    let dependents: Vec<usize> = (0..entries.len())
+        .filter(|&i| entries[i].requires.iter().any(|r| r == entry.name))
+        .collect();
+    for (position, &dependent) in dependents.iter().enumerate() {
+        let (branch, next) = if position + 1 == dependents.len() {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        print_branch(
+            entries,
+            dependent,
+            &format!("{}{}", indent, branch),
+            &format!("{}{}", indent, next),
+            shown,
+        );
+    }
+}
+
+fn print_table(listing: &Listing, sections: &[Section]) {
+    let name_width = listing
+        .exercises
+        .iter()
+        .map(|entry| entry.name.len())
+        .chain(Some("Name".len()))
+        .max()
+        .unwrap_or(0);
+    let path_width = listing
+        .exercises
+        .iter()
+        .map(|entry| entry.path.to_string_lossy().len())
+        .chain(Some("Path".len()))
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{:name_width$}  {:path_width$}  {:7}  {:10}  State",
+        "Name",
+        "Path",
+        "Mode",
+        "Difficulty",
+        name_width = name_width,
+        path_width = path_width
+    );
+    let mut current_section = None;
+    for entry in &listing.exercises {
+        if let Some(section) = entry.section.filter(|_| entry.section != current_section) {
+            let title = sections
+                .iter()
+                .find(|s| s.name == section)
+                .map_or(section, Section::title);
+            println!();
+            println!("{}", style(title).bold());
+            current_section = entry.section;
+        }
+        let state = styled_state(entry);
+        let difficulty = entry
+            .difficulty
+            .map_or(String::from("-"), |d| d.to_string());
+        println!(
+            "{:name_width$}  {:path_width$}  {:7}  {:10}  {}",
+            entry.name,
+            entry.path.display(),
+            entry.mode,
+            difficulty,
+            state,
+            name_width = name_width,
+            path_width = path_width
+        );
+    }
+
+    let summary = &listing.summary;
+    println!();
+    println!("{}", completion(summary.done, summary.total));
+}
+
+// The state of the exercise of the given row, as shown in the tables
+fn styled_state(entry: &Entry) -> StyledObject<&'static str> {
+    if entry.missing {
+        style("Missing").yellow()
+    } else if entry.done {
+        style("Done").green()
+    } else {
+        style("Pending").red()
+    }
+}
+
+// Describe how many of the exercises are done
+pub fn completion(done: usize, total: usize) -> String {
+    let percentage = if total == 0 {
+        0.0
+    } else {
+        done as f64 * 100.0 / total as f64
+    };
+    format!(
+        "Progress: You completed {} / {} exercises ({:.1} %).",
+        done, total, percentage
+    )
+}
@@ -0,0 +1,179 @@
+use crate::exercise::{Exercise, Mode, EDITION};
+use crate::support::SUPPORT_LIB;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const PROJECT_FILE_PATH: &str = "rust-project.json";
+
+// The project description rust-analyzer reads in place of a Cargo.toml.
+// See https://rust-analyzer.github.io/manual.html#non-cargo-based-projects
+#[derive(Serialize)]
+struct RustProject<'a> {
+    // The sources of the standard library, for completion of std items
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sysroot_src: Option<PathBuf>,
+    crates: Vec<Crate<'a>>,
+}
+
+// A single exercise, seen as a crate of its own, or the support library
+#[derive(Serialize)]
+struct Crate<'a> {
+    root_module: &'a Path,
+    edition: &'static str,
+    deps: Vec<Dep>,
+    cfg: Vec<&'static str>,
+}
+
+// A dependency of a crate, by its index in the list of crates
+#[derive(Serialize)]
+struct Dep {
+    #[serde(rename = "crate")]
+    krate: usize,
+    name: &'static str,
+}
+
+// Write the rust-analyzer project file declaring every exercise as a crate,
+// so editors offer completion and inline errors for the exercises
+pub fn lsp(exercises: &[Exercise]) -> Result<(), ()> {
+    let sysroot_src = sysroot_src();
+    let project = project_file(exercises, &sysroot_src, support_lib());
+    if let Err(e) = fs::write(PROJECT_FILE_PATH, project) {
+        warn!("Could not write {}", PROJECT_FILE_PATH);
+        println!("{}", e);
+        return Err(());
+    }
+
+    success!("Generated {} for rust-analyzer", PROJECT_FILE_PATH);
+    if !sysroot_src.as_ref().is_some_and(|src| src.exists()) {
+        println!("Install the standard library sources with `rustup component add rust-src`");
+        println!("to get completion for the items of the standard library as well.");
+    }
+    println!("It is kept up to date whenever the exercise list changes.");
+    Ok(())
+}
+
+// Regenerate the project file if it was generated before
+// and no longer matches the exercise list
+pub fn refresh_project_file(exercises: &[Exercise]) -> io::Result<()> {
+    let current = match fs::read_to_string(PROJECT_FILE_PATH) {
+        Ok(current) => current,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let generated = project_file(exercises, &sysroot_src(), support_lib());
+    if current != generated {
+        fs::write(PROJECT_FILE_PATH, generated)?;
+    }
+    Ok(())
+}
+
+// The support library comes first, so every exercise can depend on it as crate 0
+fn project_file(
+    exercises: &[Exercise],
+    sysroot_src: &Option<PathBuf>,
+    support: Option<&Path>,
+) -> String {
+    let mut crates: Vec<Crate> = support
+        .map(|root_module| Crate {
+            root_module,
+            edition: EDITION,
+            deps: Vec::new(),
+            cfg: Vec::new(),
+        })
+        .into_iter()
+        .collect();
+    crates.extend(exercises.iter().map(|exercise| {
+        Crate {
+            root_module: &exercise.path,
+            edition: EDITION,
+            deps: support
+                .map(|_| Dep {
+                    krate: 0,
+                    name: "support",
+                })
+                .into_iter()
+                .collect(),
+            cfg: match exercise.mode {
+                Mode::Test => vec!["test"],
+                _ => Vec::new(),
+            },
+        }
+    }));
+    let project = RustProject {
+        sysroot_src: sysroot_src.clone(),
+        crates,
+    };
+    serde_json::to_string_pretty(&project).unwrap() + "\n"
+}
+
+// The root module of the support library, if there is one
+fn support_lib() -> Option<&'static Path> {
+    Some(Path::new(SUPPORT_LIB)).filter(|lib| lib.exists())
+}
+
+// The sources of the standard library of the active toolchain
+fn sysroot_src() -> Option<PathBuf> {
+    let output = Command::new("rustc")
+        .args(["--print", "sysroot"])
+        .output()
+        .ok()?;
+    let sysroot = String::from_utf8(output.stdout).ok()?;
+    Some(Path::new(sysroot.trim()).join("lib/rustlib/src/rust/library"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn merge_sort() -> Exercise {
+        Exercise {
+            name: "merge_sort".into(),
+            path: PathBuf::from("fundamentals/algorithms/sorts/merge_sort.rs"),
+            mode: Mode::Test,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_project_file() {
+        let project: serde_json::Value =
+            serde_json::from_str(&project_file(&[merge_sort()], &None, None)).unwrap();
+        assert_eq!(
+            project,
+            serde_json::json!({
+                "crates": [{
+                    "root_module": "fundamentals/algorithms/sorts/merge_sort.rs",
+                    "edition": "2018",
+                    "deps": [],
+                    "cfg": ["test"],
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_project_file_with_support() {
+        let support = Path::new("fundamentals/support/lib.rs");
+        let project: serde_json::Value =
+            serde_json::from_str(&project_file(&[merge_sort()], &None, Some(support))).unwrap();
+        assert_eq!(
+            project,
+            serde_json::json!({
+                "crates": [{
+                    "root_module": "fundamentals/support/lib.rs",
+                    "edition": "2018",
+                    "deps": [],
+                    "cfg": [],
+                }, {
+                    "root_module": "fundamentals/algorithms/sorts/merge_sort.rs",
+                    "edition": "2018",
+                    "deps": [{"crate": 0, "name": "support"}],
+                    "cfg": ["test"],
+                }]
+            })
+        );
+    }
+}
@@ -1,26 +1,16 @@
-use crate::exercise::{Exercise, ExerciseList};
-use crate::run::run;
-use crate::verify::verify;
-use clap::{crate_version, App, Arg, SubCommand};
+use clap::{crate_version, App, Arg, ArgMatches, SubCommand};
 use console::Emoji;
-use notify::DebouncedEvent;
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use std::ffi::OsStr;
+use rust_cs_fundamentals::commands::{
+    bench, check_content, explain, hint, list, lsp, refresh_project_file, reset, solution, tracks,
+    watch, write_report, Filter, Format, Schedule, WatchStatus, PROJECT_FILE_PATH,
+};
+use rust_cs_fundamentals::scratch::{exit, ScratchDir};
+use rust_cs_fundamentals::{
+    check_toolchain, run, verify, Exercise, ExerciseList, ExerciseResult, Progress, Scope, Track,
+    TRACKS_DIR,
+};
 use std::fs;
-use std::io;
 use std::path::Path;
-use std::process::{Command, Stdio};
-use std::sync::mpsc::channel;
-use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::Duration;
-
-#[macro_use]
-mod ui;
-
-mod exercise;
-mod run;
-mod verify;
 
 fn main() {
     let matches = App::new("algo")
@@ -32,27 +22,163 @@ fn main() {
                 .long("nocapture")
                 .help("Show outputs from the test exercises")
         )
+        .arg(
+            Arg::with_name("track")
+                .long("track")
+                .takes_value(true)
+                .help("Switches to the track with the given name, whose exercises are listed in tracks/<name>.toml")
+        )
         .subcommand(
             SubCommand::with_name("verify")
                 .alias("v")
                 .about("Verifies all exercises according to the recommended order")
+                .arg(
+                    Arg::with_name("jobs")
+                        .short("j")
+                        .long("jobs")
+                        .takes_value(true)
+                        .default_value("1")
+                        .validator(|jobs| match jobs.parse::<usize>() {
+                            Ok(jobs) if jobs > 0 => Ok(()),
+                            _ => Err(String::from("the number of jobs must be a positive integer")),
+                        })
+                        .help("Compiles up to N exercises in parallel"),
+                )
+                .args(&scope_args())
+                .args(&report_args()),
         )
         .subcommand(
             SubCommand::with_name("watch")
                 .alias("w")
                 .about("Reruns `verify` when files were edited")
+                .arg(
+                    Arg::with_name("schedule")
+                        .long("schedule")
+                        .takes_value(true)
+                        .possible_values(&["order", "prerequisites"])
+                        .default_value("order")
+                        .help("Works on the exercises in order, or on any exercise whose prerequisites are done"),
+                )
+                .args(&scope_args()),
         )
         .subcommand(
             SubCommand::with_name("run")
                 .alias("r")
                 .about("Runs/Tests a single exercise")
+                .arg(Arg::with_name("name").required(true).index(1))
+                .args(&report_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("bench")
+                .alias("b")
+                .about("Times an exercise, compiled with optimizations, against the standard library")
                 .arg(Arg::with_name("name").required(true).index(1)),
         )
+        .subcommand(
+            SubCommand::with_name("list")
+                .alias("l")
+                .about("Lists the exercises and whether they are done")
+                .arg(
+                    Arg::with_name("solved")
+                        .long("solved")
+                        .conflicts_with("pending")
+                        .help("Only lists the exercises which are done"),
+                )
+                .arg(
+                    Arg::with_name("pending")
+                        .long("pending")
+                        .help("Only lists the exercises which are still pending"),
+                )
+                .arg(
+                    Arg::with_name("mode")
+                        .long("mode")
+                        .takes_value(true)
+                        .possible_values(&["compile", "test", "clippy", "output"])
+                        .help("Only lists the exercises of the given mode"),
+                )
+                .arg(
+                    Arg::with_name("filter")
+                        .long("filter")
+                        .takes_value(true)
+                        .help("Only lists the exercises whose name or path contains the given text"),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .takes_value(true)
+                        .possible_values(&["table", "json"])
+                        .default_value("table")
+                        .help("The output format of the list"),
+                )
+                .arg(
+                    Arg::with_name("tree")
+                        .long("tree")
+                        .help("Lists every exercise under the exercises it requires"),
+                )
+                .args(&scope_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("reset")
+                .about("Restores an exercise to its original stub")
+                .arg(
+                    Arg::with_name("name")
+                        .required_unless("all")
+                        .conflicts_with("all")
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("all")
+                        .long("all")
+                        .help("Restores every exercise"),
+                )
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .help("Overwrites modified exercises after backing them up"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("solution")
+                .about("Shows the reference solution of a completed exercise")
+                .arg(Arg::with_name("name").required(true).index(1))
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .help("Shows the solution even if the exercise is not done"),
+                )
+                .arg(
+                    Arg::with_name("diff")
+                        .long("diff")
+                        .help("Shows the solution side by side with your version"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("tracks")
+                .about("Lists the tracks and how many of their exercises are done"),
+        )
+        .subcommand(
+            SubCommand::with_name("check-content")
+                .about("Checks that info.toml, the exercises and their solutions are consistent"),
+        )
+        .subcommand(
+            SubCommand::with_name("explain")
+                .about("Explains a compiler error code, such as E0308")
+                .arg(Arg::with_name("code").required(true).index(1)),
+        )
+        .subcommand(
+            SubCommand::with_name("lsp")
+                .about("Generates rust-project.json so that rust-analyzer understands the exercises"),
+        )
         .subcommand(
             SubCommand::with_name("hint")
                 .alias("h")
-                .about("Returns a hint for the current exercise")
-                .arg(Arg::with_name("name").required(true).index(1)),
+                .about("Reveals the next hint for the given exercise")
+                .arg(Arg::with_name("name").required(true).index(1))
+                .arg(
+                    Arg::with_name("all")
+                        .long("all")
+                        .help("Shows every hint which is unlocked"),
+                ),
         )
         .get_matches();
 
@@ -62,40 +188,102 @@ fn main() {
         println!();
     }
 
-    if !Path::new("info.toml").exists() {
+    if !Path::new("info.toml").exists() && !Path::new(TRACKS_DIR).is_dir() {
         println!(
             "{} must be run from the rust-cs-fundamentals directory",
             std::env::current_exe().unwrap().to_str().unwrap()
         );
         println!("Try `cd rust-cs-fundamentals/`!");
-        std::process::exit(1);
+        exit(1);
     }
 
-    if !rustc_exists() {
+    if check_toolchain().is_err() {
         println!("We cannot find `rustc`.");
         println!("Try running `rustc --version` to diagnose your problem.");
         println!("For instructions on how to install Rust, check the README.");
-        std::process::exit(1);
+        exit(1);
     }
 
-    let toml_str = &fs::read_to_string("info.toml").unwrap();
-    let exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
+    // Every exercise is compiled in the scratch directory of this run,
+    // removed once the guard goes out of scope
+    let _scratch = ScratchDir::create().unwrap_or_else(|e| {
+        println!(
+            "Could not create the directory the exercises are compiled in: {}",
+            e
+        );
+        exit(1)
+    });
+
+    let track = match matches.value_of("track") {
+        Some(name) => {
+            let track = Track::named(name).unwrap_or_else(|| {
+                println!("No track is named `{}`.", name);
+                println!("Run `algo tracks` to see the available tracks.");
+                exit(1)
+            });
+            if let Err(e) = track.select() {
+                println!("Could not remember the track you chose: {}", e);
+            }
+            if matches.subcommand_name().is_none() {
+                println!("You are now working on the {} track.", track.name);
+            }
+            track
+        }
+        None => Track::current().unwrap_or_else(|| {
+            println!(
+                "There is no track to work on, neither info.toml nor {}/<name>.toml exists.",
+                TRACKS_DIR
+            );
+            exit(1)
+        }),
+    };
+
+    if matches.subcommand_matches("tracks").is_some() {
+        tracks(&track).unwrap_or_else(|_| exit(1));
+        return;
+    }
+
+    let exercise_list = track.load().unwrap_or_else(|e| {
+        println!("Could not load the exercise list: {}", e);
+        exit(1)
+    });
+    let exercises = &exercise_list.exercises;
+    let sections = &exercise_list.sections;
     let verbose = matches.is_present("nocapture");
+    let progress_path = track.progress_path();
+    let mut progress = Progress::load(&progress_path).unwrap_or_else(|e| {
+        println!(
+            "Could not read your progress from {}: {}",
+            progress_path.display(),
+            e
+        );
+        println!("Fix or remove the file to start over.");
+        exit(1)
+    });
 
-    if let Some(ref matches) = matches.subcommand_matches("run") {
+    if matches.subcommand_matches("lsp").is_some() {
+        lsp(exercises).unwrap_or_else(|_| exit(1));
+    } else if let Err(e) = refresh_project_file(exercises) {
+        println!("Could not update {}: {}", PROJECT_FILE_PATH, e);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("run") {
         let name = matches.value_of("name").unwrap();
 
         let matching_exercise = |e: &&Exercise| name == e.name;
 
         let exercise = exercises.iter().find(matching_exercise).unwrap_or_else(|| {
             println!("No exercise found for your given name!");
-            std::process::exit(1)
+            exit(1)
         });
 
-        run(&exercise, verbose).unwrap_or_else(|_| std::process::exit(1));
+        let mut results = Vec::new();
+        let ran = run(exercise, &mut progress, &mut results, verbose);
+        write_requested_report(matches, &results);
+        ran.unwrap_or_else(|_| exit(1));
     }
 
-    if let Some(ref matches) = matches.subcommand_matches("hint") {
+    if let Some(matches) = matches.subcommand_matches("bench") {
         let name = matches.value_of("name").unwrap();
 
         let exercise = exercises
@@ -103,21 +291,120 @@ fn main() {
             .find(|e| name == e.name)
             .unwrap_or_else(|| {
                 println!("No exercise found for your given name!");
-                std::process::exit(1)
+                exit(1)
             });
 
-        println!("{}", exercise.hint);
+        bench(exercise, &mut progress).unwrap_or_else(|_| exit(1));
     }
 
-    if matches.subcommand_matches("verify").is_some() {
-        verify(&exercises, verbose).unwrap_or_else(|_| std::process::exit(1));
+    if let Some(matches) = matches.subcommand_matches("list") {
+        let filter = Filter {
+            solved: matches.is_present("solved"),
+            pending: matches.is_present("pending"),
+            mode: matches.value_of("mode").map(|mode| mode.parse().unwrap()),
+            text: matches.value_of("filter"),
+        };
+        let format = match matches.value_of("format") {
+            _ if matches.is_present("tree") => Format::Tree,
+            Some("json") => Format::Json,
+            _ => Format::Table,
+        };
+
+        let scoped = scoped_exercises(matches, &exercise_list, &track.manifest);
+        list(&scoped, sections, &progress, &filter, format);
     }
 
-    if matches.subcommand_matches("watch").is_some() {
-        if let Err(e) = watch(&exercises, verbose) {
-            println!("Error: Could not watch your progess. Error message was {:?}.", e);
-            println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
-            std::process::exit(1);
+    if let Some(matches) = matches.subcommand_matches("reset") {
+        let force = matches.is_present("force");
+        let to_reset: Vec<&Exercise> = match matches.value_of("name") {
+            Some(name) => {
+                let exercise = exercises
+                    .iter()
+                    .find(|e| name == e.name)
+                    .unwrap_or_else(|| {
+                        println!("No exercise found for your given name!");
+                        exit(1)
+                    });
+                vec![exercise]
+            }
+            None => exercises.iter().collect(),
+        };
+
+        let failures = to_reset
+            .into_iter()
+            .filter(|exercise| reset(exercise, &mut progress, force).is_err())
+            .count();
+        if failures > 0 {
+            exit(1);
+        }
+    }
+
+    if let Some(matches) = matches.subcommand_matches("solution") {
+        let name = matches.value_of("name").unwrap();
+
+        let exercise = exercises
+            .iter()
+            .find(|e| name == e.name)
+            .unwrap_or_else(|| {
+                println!("No exercise found for your given name!");
+                exit(1)
+            });
+
+        solution(
+            exercise,
+            &progress,
+            matches.is_present("force"),
+            matches.is_present("diff"),
+        )
+        .unwrap_or_else(|_| exit(1));
+    }
+
+    if matches.subcommand_matches("check-content").is_some() {
+        check_content(&exercise_list, &track.manifest).unwrap_or_else(|_| exit(1));
+    }
+
+    if let Some(matches) = matches.subcommand_matches("explain") {
+        explain(matches.value_of("code").unwrap()).unwrap_or_else(|_| exit(1));
+    }
+
+    if let Some(matches) = matches.subcommand_matches("hint") {
+        let name = matches.value_of("name").unwrap();
+
+        let exercise = exercises
+            .iter()
+            .find(|e| name == e.name)
+            .unwrap_or_else(|| {
+                println!("No exercise found for your given name!");
+                exit(1)
+            });
+
+        hint(exercise, &mut progress, matches.is_present("all")).unwrap_or_else(|_| exit(1));
+    }
+
+    if let Some(matches) = matches.subcommand_matches("verify") {
+        let jobs = matches.value_of("jobs").unwrap().parse().unwrap();
+        let scoped = scoped_exercises(matches, &exercise_list, &track.manifest);
+        let mut results = Vec::new();
+        let verified = verify(&scoped, &mut progress, &mut results, verbose, jobs);
+        write_requested_report(matches, &results);
+        verified.unwrap_or_else(|_| exit(1));
+    }
+
+    if let Some(matches) = matches.subcommand_matches("watch") {
+        let scoped = scoped_exercises(matches, &exercise_list, &track.manifest);
+        let schedule: Schedule = matches.value_of("schedule").unwrap().parse().unwrap();
+        let root = exercise_list.watch_root();
+        match watch(&scoped, sections, &root, &mut progress, verbose, schedule) {
+            Err(e) => {
+                println!(
+                    "Error: Could not watch your progess. Error message was {:?}.",
+                    e
+                );
+                println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
+                exit(1);
+            }
+            Ok(WatchStatus::Quit) => return,
+            Ok(WatchStatus::Finished) => {}
         }
         println!(
             "{emoji} All exercises completed! {emoji}",
@@ -135,81 +422,76 @@ fn main() {
     }
 }
 
-fn spawn_watch_shell(failed_exercise_hint: &Arc<Mutex<Option<String>>>) {
-    let failed_exercise_hint = Arc::clone(failed_exercise_hint);
-    println!("Type 'hint' to get help or 'clear' to clear the screen");
-    thread::spawn(move || loop {
-        let mut input = String::new();
-        match io::stdin().read_line(&mut input) {
-            Ok(_) => {
-                let input = input.trim();
-                if input.eq("hint") {
-                    if let Some(hint) = &*failed_exercise_hint.lock().unwrap() {
-                        println!("{}", hint);
-                    }
-                } else if input.eq("clear") {
-                    println!("\x1B[2J\x1B[1;1H");
-                } else {
-                    println!("unknown command: {}", input);
-                }
-            }
-            Err(error) => println!("error reading command: {}", error),
-        }
-    });
+// The arguments of the subcommands which can work on a part of the exercises only
+fn scope_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("section")
+            .long("section")
+            .takes_value(true)
+            .help("Only works on the exercises of the given section, such as `sorts`"),
+        Arg::with_name("tag")
+            .long("tag")
+            .takes_value(true)
+            .help("Only works on the exercises with the given tag"),
+    ]
 }
 
-fn watch(exercises: &[Exercise], verbose: bool) -> notify::Result<()> {
-    /* Clears the terminal with an ANSI escape code.
-    Works in UNIX and newer Windows terminals. */
-    fn clear_screen() {
-        println!("\x1Bc");
-    }
-
-    let (tx, rx) = channel();
-
-    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(2))?;
-    watcher.watch(Path::new("./fundamentals"), RecursiveMode::Recursive)?;
-
-    clear_screen();
-
-    let to_owned_hint = |t: &Exercise| t.hint.to_owned();
-    let failed_exercise_hint = match verify(exercises.iter(), verbose) {
-        Ok(_) => return Ok(()),
-        Err(exercise) => Arc::new(Mutex::new(Some(to_owned_hint(exercise)))),
+// The exercises in the section and with the tag the end user asked for
+fn scoped_exercises(
+    matches: &ArgMatches,
+    exercise_list: &ExerciseList,
+    manifest: &Path,
+) -> Vec<Exercise> {
+    let scope = Scope {
+        section: matches.value_of("section"),
+        tag: matches.value_of("tag"),
     };
-    spawn_watch_shell(&failed_exercise_hint);
-    loop {
-        match rx.recv() {
-            Ok(event) => match event {
-                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b) => {
-                    if b.extension() == Some(OsStr::new("rs")) && b.exists() {
-                        let filepath = b.as_path().canonicalize().unwrap();
-                        let pending_exercises = exercises
-                            .iter()
-                            .skip_while(|e| !filepath.ends_with(&e.path));
-                        clear_screen();
-                        match verify(pending_exercises, verbose) {
-                            Ok(_) => return Ok(()),
-                            Err(exercise) => {
-                                let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
-                                *failed_exercise_hint = Some(to_owned_hint(exercise));
-                            }
-                        }
-                    }
-                }
-                _ => {}
-            },
-            Err(e) => println!("watch error: {:?}", e),
+    if let Some(section) = scope.section {
+        if exercise_list.section(section).is_none() {
+            let names: Vec<&str> = exercise_list
+                .sections
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect();
+            println!("No section is named `{}`.", section);
+            if names.is_empty() {
+                println!("{} doesn't declare any section.", manifest.display());
+            } else {
+                println!("The sections are: {}", names.join(", "));
+            }
+            exit(1);
         }
     }
+    let scoped = exercise_list.scoped(scope);
+    if scoped.is_empty() {
+        println!("No exercise matches the given section and tag!");
+        exit(1);
+    }
+    scoped
+}
+
+// The arguments of the subcommands which can write a report for other tools
+fn report_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("report")
+            .long("report")
+            .takes_value(true)
+            .possible_values(&["json", "junit", "tap"])
+            .requires("output")
+            .help("Writes a report of the results in the given format"),
+        Arg::with_name("output")
+            .long("output")
+            .takes_value(true)
+            .requires("report")
+            .help("The file the report is written to"),
+    ]
 }
 
-fn rustc_exists() -> bool {
-    Command::new("rustc")
-        .args(&["--version"])
-        .stdout(Stdio::null())
-        .spawn()
-        .and_then(|mut child| child.wait())
-        .map(|status| status.success())
-        .unwrap_or(false)
+fn write_requested_report(matches: &ArgMatches, results: &[ExerciseResult]) {
+    if let (Some(format), Some(output)) = (matches.value_of("report"), matches.value_of("output")) {
+        if let Err(e) = write_report(Path::new(output), format.parse().unwrap(), results) {
+            println!("Could not write the report to {}: {}", output, e);
+            exit(1);
+        }
+    }
 }
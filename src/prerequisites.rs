@@ -0,0 +1,146 @@
+use crate::exercise::Exercise;
+use crate::progress::Progress;
+use std::collections::HashMap;
+
+// Where an exercise stands while looking for cycles among the prerequisites
+#[derive(Copy, Clone, PartialEq)]
+enum Mark {
+    Unvisited,
+    // The exercise is one of the prerequisites being followed
+    OnPath,
+    // Every prerequisite of the exercise was followed without finding a cycle
+    Visited,
+}
+
+// Check that every prerequisite is a listed exercise, and that no exercise
+// requires itself, either directly or through its prerequisites
+pub fn check_prerequisites(exercises: &[Exercise]) -> Result<(), String> {
+    let indices: HashMap<&str, usize> = exercises
+        .iter()
+        .enumerate()
+        .map(|(index, exercise)| (exercise.name.as_str(), index))
+        .collect();
+    for exercise in exercises {
+        if let Some(unknown) = exercise
+            .requires
+            .iter()
+            .find(|name| !indices.contains_key(name.as_str()))
+        {
+            return Err(format!(
+                "{} requires `{}`, which is not listed",
+                exercise.name, unknown
+            ));
+        }
+    }
+
+    let mut marks = vec![Mark::Unvisited; exercises.len()];
+    let mut path = Vec::new();
+    for index in 0..exercises.len() {
+        follow(index, exercises, &indices, &mut marks, &mut path)?;
+    }
+    Ok(())
+}
+
+// Follow the prerequisites of the exercise at the given index, depth first.
+// Coming back to an exercise on the current path means there is a cycle
+fn follow(
+    index: usize,
+    exercises: &[Exercise],
+    indices: &HashMap<&str, usize>,
+    marks: &mut [Mark],
+    path: &mut Vec<usize>,
+) -> Result<(), String> {
+    match marks[index] {
+        Mark::Visited => return Ok(()),
+        Mark::OnPath => {
+            let start = path.iter().position(|&i| i == index).unwrap();
+            let cycle: Vec<&str> = path[start..]
+                .iter()
+                .chain(Some(&index))
+                .map(|&i| exercises[i].name.as_str())
+                .collect();
+            return Err(format!(
+                "The prerequisites form a cycle: {}",
+                cycle.join(" requires ")
+            ));
+        }
+        Mark::Unvisited => {}
+    }
+
+    marks[index] = Mark::OnPath;
+    path.push(index);
+    for name in &exercises[index].requires {
+        follow(indices[name.as_str()], exercises, indices, marks, path)?;
+    }
+    path.pop();
+    marks[index] = Mark::Visited;
+    Ok(())
+}
+
+// The prerequisites of the exercise which are not done yet, among the given
+// exercises. Prerequisites outside of them, such as those of another
+// section, are not waited for
+pub fn missing_prerequisites<'a>(
+    exercise: &Exercise,
+    exercises: &'a [Exercise],
+    progress: &Progress,
+) -> Vec<&'a Exercise> {
+    exercises
+        .iter()
+        .filter(|e| exercise.requires.contains(&e.name))
+        .filter(|e| !e.is_done(progress))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::ExerciseList;
+
+    fn exercises(requires: &[(&str, &[&str])]) -> Vec<Exercise> {
+        let mut info = String::new();
+        for (name, requires) in requires {
+            let requires: Vec<String> = requires.iter().map(|r| format!("{:?}", r)).collect();
+            info.push_str(&format!(
+                "[[exercises]]\nname = {:?}\npath = \"{}.rs\"\nmode = \"test\"\nrequires = [{}]\n",
+                name,
+                name,
+                requires.join(", ")
+            ));
+        }
+        toml::from_str::<ExerciseList>(&info).unwrap().exercises
+    }
+
+    #[test]
+    fn test_check_prerequisites() {
+        let graph = exercises(&[
+            ("bubble_sort", &[]),
+            ("merge_sort", &["bubble_sort"]),
+            ("heap_sort", &["merge_sort", "bubble_sort"]),
+        ]);
+        assert_eq!(check_prerequisites(&graph), Ok(()));
+
+        let unknown = exercises(&[("merge_sort", &["bubble_sort"])]);
+        assert_eq!(
+            check_prerequisites(&unknown),
+            Err(String::from(
+                "merge_sort requires `bubble_sort`, which is not listed"
+            ))
+        );
+
+        let cycle = exercises(&[
+            ("bubble_sort", &[]),
+            ("merge_sort", &["heap_sort"]),
+            ("heap_sort", &["bubble_sort", "merge_sort"]),
+        ]);
+        assert_eq!(
+            check_prerequisites(&cycle),
+            Err(String::from(
+                "The prerequisites form a cycle: merge_sort requires heap_sort requires merge_sort"
+            ))
+        );
+
+        let itself = exercises(&[("merge_sort", &["merge_sort"])]);
+        assert!(check_prerequisites(&itself).is_err());
+    }
+}
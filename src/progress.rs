@@ -0,0 +1,320 @@
+use crate::bench::Benchmark;
+use crate::exercise::Exercise;
+use crate::support::build_fingerprint;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const PROGRESS_FILE_PATH: &str = ".algo/progress.toml";
+const PROGRESS_VERSION: u32 = 1;
+// The number of benchmarks kept for every exercise
+const KEPT_BENCHMARKS: usize = 10;
+
+// The persisted progress of every exercise.
+// This is (de)serialized from the progress file
+#[derive(Serialize, Deserialize)]
+pub struct Progress {
+    // The version of the progress file format
+    pub version: u32,
+    // The progress of each exercise, keyed by the exercise name
+    #[serde(default)]
+    pub exercises: BTreeMap<String, ExerciseProgress>,
+    // The file this progress is loaded from and saved to
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+// The recorded history of a single exercise
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct ExerciseProgress {
+    // Whether the exercise has passed at least once
+    pub solved: bool,
+    // Whether the last attempt passed
+    pub passed: bool,
+    // The number of times the exercise has been verified or run
+    pub attempts: u32,
+    // The number of attempts that failed
+    pub failures: u32,
+    // The time of the last attempt, in seconds since the Unix epoch
+    pub last_attempt: Option<u64>,
+    // The time of the first successful attempt, in seconds since the Unix epoch
+    pub solved_at: Option<u64>,
+    // The hash of the source code that last passed
+    pub passed_hash: Option<String>,
+    // The version of the compiler, and of the support library if any,
+    // the exercise last passed with
+    pub passed_toolchain: Option<String>,
+    // The number of hint levels revealed so far
+    #[serde(default)]
+    pub hints_revealed: u32,
+    // The last benchmarks of the exercise, the latest one last
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub benchmarks: Vec<Benchmark>,
+}
+
+impl Progress {
+    // Create an empty progress which will be saved to the given path
+    pub fn new(path: impl Into<PathBuf>) -> Progress {
+        Progress {
+            version: PROGRESS_VERSION,
+            exercises: BTreeMap::new(),
+            path: path.into(),
+        }
+    }
+
+    // Load the progress from the given path.
+    // A missing file is not an error, it simply means nothing has been attempted yet
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Progress> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Progress::new(path));
+        }
+
+        let content = fs::read_to_string(path)?;
+        let mut progress: Progress =
+            toml::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if progress.version > PROGRESS_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "progress file version {} is newer than the supported version {}",
+                    progress.version, PROGRESS_VERSION
+                ),
+            ));
+        }
+        progress.version = PROGRESS_VERSION;
+        progress.path = path.to_path_buf();
+        Ok(progress)
+    }
+
+    // Write the progress to its file.
+    // The content is written to a temporary file first so that a concurrent
+    // reader never sees a half written progress file
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let temp_path = self.path.with_extension(format!("toml.{}", process::id()));
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &self.path)
+    }
+
+    // Get the recorded progress of the given exercise, if any
+    pub fn get(&self, exercise: &Exercise) -> Option<&ExerciseProgress> {
+        self.exercises.get(&exercise.name)
+    }
+
+    // Whether the given exercise has been recorded as passed at least once
+    pub fn is_solved(&self, exercise: &Exercise) -> bool {
+        self.get(exercise).is_some_and(|p| p.solved)
+    }
+
+    // Whether the last attempt at the given exercise passed with the
+    // current source code and compiler, so it doesn't need to be verified again
+    pub fn is_cached(&self, exercise: &Exercise) -> bool {
+        match self.get(exercise) {
+            Some(recorded) if recorded.passed => {
+                recorded.passed_hash.is_some()
+                    && recorded.passed_hash == exercise.source_hash().ok()
+                    && recorded.passed_toolchain == Some(build_fingerprint())
+            }
+            _ => false,
+        }
+    }
+
+    // Record the outcome of an attempt at the given exercise, along with the
+    // hash of the source it was compiled from, as taken before compiling it
+    pub fn record(&mut self, exercise: &Exercise, passed: bool, source_hash: Option<String>) {
+        let now = now();
+        let entry = self.exercises.entry(exercise.name.clone()).or_default();
+
+        entry.attempts += 1;
+        entry.passed = passed;
+        entry.last_attempt = Some(now);
+        if passed {
+            if !entry.solved {
+                entry.solved_at = Some(now);
+            }
+            entry.solved = true;
+            entry.passed_hash = source_hash;
+            entry.passed_toolchain = Some(build_fingerprint());
+        } else {
+            entry.failures += 1;
+        }
+    }
+
+    // Forget that the given exercise has been solved.
+    // The attempt history is kept, only the completion is cleared
+    pub fn reset(&mut self, exercise: &Exercise) {
+        if let Some(entry) = self.exercises.get_mut(&exercise.name) {
+            entry.solved = false;
+            entry.passed = false;
+            entry.solved_at = None;
+            entry.passed_hash = None;
+            entry.passed_toolchain = None;
+        }
+    }
+
+    // Record that the given number of hint levels of the exercise have been revealed
+    pub fn reveal_hints(&mut self, exercise: &Exercise, count: u32) {
+        let entry = self.exercises.entry(exercise.name.clone()).or_default();
+        entry.hints_revealed = entry.hints_revealed.max(count);
+    }
+
+    // The recorded benchmarks of the given exercise, the latest one last
+    pub fn benchmarks(&self, exercise: &Exercise) -> &[Benchmark] {
+        self.get(exercise).map_or(&[], |p| &p.benchmarks)
+    }
+
+    // Record a benchmark of the given exercise, forgetting the oldest ones
+    pub fn record_benchmark(&mut self, exercise: &Exercise, benchmark: Benchmark) {
+        let entry = self.exercises.entry(exercise.name.clone()).or_default();
+        entry.benchmarks.push(benchmark);
+        let excess = entry.benchmarks.len().saturating_sub(KEPT_BENCHMARKS);
+        entry.benchmarks.drain(..excess);
+    }
+
+    // Record the outcome of an attempt and save the progress right away.
+    // Failing to save is reported but does not abort the current command
+    pub fn record_and_save(
+        &mut self,
+        exercise: &Exercise,
+        passed: bool,
+        source_hash: Option<String>,
+    ) {
+        self.record(exercise, passed, source_hash);
+        if let Err(e) = self.save() {
+            warn!("Could not save your progress: {}", e);
+        }
+    }
+}
+
+// The current time, in seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bench::Timing;
+    use crate::exercise::{Mode, Whitespace};
+
+    fn exercise() -> Exercise {
+        Exercise {
+            name: "finished_exercise".into(),
+            path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
+            mode: Mode::Compile,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_record_attempts() {
+        let exercise = exercise();
+        let mut progress = Progress::new("unused.toml");
+        progress.record(&exercise, false, exercise.source_hash().ok());
+        assert!(!progress.is_solved(&exercise));
+
+        progress.record(&exercise, true, exercise.source_hash().ok());
+        progress.record(&exercise, false, exercise.source_hash().ok());
+        let recorded = progress.get(&exercise).unwrap();
+        assert!(recorded.solved);
+        assert!(!recorded.passed);
+        assert_eq!(recorded.attempts, 3);
+        assert_eq!(recorded.failures, 2);
+        assert_eq!(recorded.passed_hash, exercise.source_hash().ok());
+        assert!(!progress.is_cached(&exercise));
+
+        progress.record(&exercise, true, exercise.source_hash().ok());
+        assert!(progress.is_cached(&exercise));
+    }
+
+    #[test]
+    fn test_cache_needs_compiled_source() {
+        let exercise = exercise();
+        let mut progress = Progress::new("unused.toml");
+        // The source changed while the exercise was compiled
+        progress.record(&exercise, true, Some(String::from("0000000000000000")));
+        assert!(progress.is_solved(&exercise));
+        assert!(!progress.is_cached(&exercise));
+    }
+
+    #[test]
+    fn test_cache_covers_settings() {
+        let exercise = exercise();
+        let mut progress = Progress::new("unused.toml");
+        progress.record(&exercise, true, exercise.source_hash().ok());
+
+        let tests = Exercise {
+            mode: Mode::Test,
+            ..exercise.clone()
+        };
+        let shorter = Exercise {
+            timeout_secs: Some(1),
+            ..exercise.clone()
+        };
+        let smaller = Exercise {
+            memory_limit_mb: Some(16),
+            ..exercise.clone()
+        };
+        let exact = Exercise {
+            whitespace: Whitespace::Exact,
+            ..exercise
+        };
+        for changed in &[tests, shorter, smaller, exact] {
+            assert!(!progress.is_cached(changed));
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let exercise = exercise();
+        let mut progress = Progress::new("unused.toml");
+        progress.record(&exercise, true, exercise.source_hash().ok());
+        progress.record_benchmark(
+            &exercise,
+            Benchmark {
+                at: 1,
+                hash: exercise.source_hash().ok(),
+                timings: vec![Timing {
+                    input: "random".into(),
+                    n: 1000,
+                    candidate: "yours".into(),
+                    nanos: 1500.5,
+                }],
+            },
+        );
+
+        let serialized = toml::to_string(&progress).unwrap();
+        let deserialized: Progress = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.version, PROGRESS_VERSION);
+        assert_eq!(deserialized.exercises, progress.exercises);
+    }
+
+    #[test]
+    fn test_keeps_last_benchmarks() {
+        let exercise = exercise();
+        let mut progress = Progress::new("unused.toml");
+        for at in 0..15 {
+            let benchmark = Benchmark {
+                at,
+                hash: None,
+                timings: Vec::new(),
+            };
+            progress.record_benchmark(&exercise, benchmark);
+        }
+        let benchmarks = progress.benchmarks(&exercise);
+        assert_eq!(benchmarks.len(), KEPT_BENCHMARKS);
+        assert_eq!(benchmarks.last().unwrap().at, 14);
+    }
+}
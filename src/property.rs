@@ -0,0 +1,103 @@
+use crate::harness::INPUT_SOURCE;
+use serde::Deserialize;
+
+// The name of the test which checks an exercise against a reference implementation
+pub const HARNESS_TEST: &str = "__algo_property::differential";
+// The generation of the cases, with the `Rng` of `harness/input.rs`,
+// and the shrinking of counterexamples
+const PROPERTY_SOURCE: &str = include_str!("../harness/property.rs");
+// The number of generated cases
+const CASES: usize = 5000;
+// The seed the cases are generated with, unless ALGO_SEED is set
+const SEED: u64 = 20_190_417;
+
+// What an exercise is checked against on generated inputs
+#[derive(Deserialize, Copy, Clone, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Property {
+    // The input is sorted like `slice::sort` sorts it
+    Sort,
+    // The index of the item is returned, or -1 if the input doesn't contain it
+    Search,
+    // Same as search, but the input is sorted first
+    SortedSearch,
+}
+
+// The source of a test harness checking the given expression against a reference
+// on thousands of generated cases, to append to the source of the exercise.
+// The expression is evaluated with a `Vec<i64>` named `input`, and an `item`
+// to look for in searches. The test fails with the smallest counterexample
+// it finds, see `harness/property.rs`
+pub fn harness(call: &str, property: Property) -> String {
+    let check = match property {
+        Property::Sort => format!(
+            r#"
+        let mut expected = case.to_vec();
+        expected.sort();
+        let actual = catch_unwind(AssertUnwindSafe(|| {{
+            let mut input = case.to_vec();
+            {call};
+            input
+        }}))
+        .map_err(|_| format!("expected: {{:?}}\n  actual: a panic", expected))?;
+        if actual != expected {{
+            return Err(format!("expected: {{:?}}\n  actual: {{:?}}", expected, actual));
+        }}
+        Ok(())"#,
+            call = call
+        ),
+        Property::Search | Property::SortedSearch => format!(
+            r#"
+        let expected = match case.iter().position(|&element| element == item) {{
+            Some(index) => format!("{{}}, the index of the item", index),
+            None => String::from("-1, the item is missing"),
+        }};
+        let actual = catch_unwind(AssertUnwindSafe(|| {{
+            let input = case.to_vec();
+            ({call}) as i64
+        }}))
+        .map_err(|_| format!("expected: {{}}\n  actual: a panic", expected))?;
+        let found = match actual {{
+            -1 => !case.contains(&item),
+            index => index >= 0 && case.get(index as usize) == Some(&item),
+        }};
+        if !found {{
+            return Err(format!("expected: {{}}\n  actual: {{}}", expected, actual));
+        }}
+        Ok(())"#,
+            call = call
+        ),
+    };
+    format!(
+        r#"
+#[cfg(test)]
+mod __algo_property {{
+    #[allow(unused_imports)]
+    use super::*;
+    use std::panic::{{catch_unwind, AssertUnwindSafe}};
+
+{input_source}
+{property_source}
+    #[allow(unused_variables)]
+    fn check(case: &[i64], item: i64) -> Result<(), String> {{{check}
+    }}
+
+    #[test]
+    fn differential() {{
+        let seed = std::env::var("ALGO_SEED")
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or({seed});
+        check_property(seed, {cases}, {search}, {sorted}, check);
+    }}
+}}
+"#,
+        input_source = INPUT_SOURCE,
+        property_source = PROPERTY_SOURCE,
+        check = check,
+        seed = SEED,
+        cases = CASES,
+        search = property != Property::Sort,
+        sorted = property == Property::SortedSearch,
+    )
+}
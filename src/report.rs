@@ -0,0 +1,615 @@
+use crate::comparisons::ComparisonReport;
+use crate::complexity::ComplexityReport;
+use crate::diagnostic::{Diagnostic, Level};
+use crate::error::Error;
+use crate::exercise::{CaseResult, Exercise, ExerciseOutput, Limit, Mode, Verdict};
+use console::strip_ansi_codes;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+// The format of a report written for other tools
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ReportFormat {
+    // A JSON document holding every result
+    Json,
+    // A JUnit XML document, with one test suite per exercise
+    Junit,
+    // A Test Anything Protocol stream, with one test point per exercise
+    Tap,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ReportFormat, String> {
+        match s {
+            "json" => Ok(ReportFormat::Json),
+            "junit" => Ok(ReportFormat::Junit),
+            "tap" => Ok(ReportFormat::Tap),
+            _ => Err(format!("unknown report format: {}", s)),
+        }
+    }
+}
+
+// The overall outcome of verifying or running an exercise
+#[derive(Serialize, Copy, Clone, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    // The exercise compiled and ran successfully
+    Passed,
+    // The exercise is unchanged since it last passed, so it wasn't compiled again
+    Cached,
+    // The exercise failed to compile
+    CompileError,
+    // The exercise compiled, but running it failed
+    Failed,
+    // The exercise was stopped for exceeding one of its limits
+    LimitExceeded,
+    // The exercise ran successfully, but didn't print the expected output
+    WrongOutput,
+    // The exercise ran successfully, but its running time grows faster than declared
+    TooSlow,
+    // The exercise ran successfully, but made more comparisons than declared
+    TooManyComparisons,
+    // The exercise ran successfully, but reordered records with equal keys
+    Unstable,
+    // The exercise ran successfully, but a generated input gave a wrong result
+    Counterexample,
+}
+
+// The result of a single step of an exercise, either compiling or running it
+#[derive(Serialize, Debug)]
+pub struct Step {
+    // Whether the step succeeded
+    pub success: bool,
+    // How long the step took, in seconds
+    pub duration_secs: f64,
+    // The captured standard output of the step
+    pub stdout: String,
+    // The captured standard error of the step
+    pub stderr: String,
+    // The diagnostics of the compiler, if the step is a failed compilation
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Step {
+    pub fn new(success: bool, duration: Duration, output: ExerciseOutput) -> Step {
+        Step {
+            success,
+            duration_secs: duration.as_secs_f64(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+            diagnostics: output.diagnostics.into_vec(),
+        }
+    }
+}
+
+// The outcome of a single test of a test harness
+#[derive(Serialize, Copy, Clone, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TestOutcome {
+    Ok,
+    Failed,
+    Ignored,
+}
+
+// A single test of a test harness, as reported by libtest
+#[derive(Serialize, PartialEq, Debug)]
+pub struct TestCase {
+    // The path of the test function, e.g. `tests::test_sorting`
+    pub name: String,
+    // Whether the test passed, failed or was ignored
+    pub outcome: TestOutcome,
+    // The output captured while running the test
+    pub output: String,
+}
+
+// The structured result of verifying or running a single exercise
+#[derive(Serialize, Debug)]
+pub struct ExerciseResult {
+    // Name of the exercise
+    pub name: String,
+    // The path to the file containing the exercise's source code
+    pub path: PathBuf,
+    // The mode of the exercise
+    pub mode: Mode,
+    // The overall outcome
+    pub status: Status,
+    // The limit the exercise exceeded, if it was stopped because of one
+    pub exceeded: Option<Limit>,
+    // The normalized output the exercise should have printed, if it printed something else
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    // The compilation step, absent if the exercise was cached
+    pub compilation: Option<Step>,
+    // The execution step, absent if the exercise wasn't run
+    pub execution: Option<Step>,
+    // The tests of the test harness, for exercises in test mode
+    pub tests: Vec<TestCase>,
+    // The cases of the exercise, if it has a cases directory
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cases: Vec<CaseResult>,
+    // How the running time grows, if the exercise declares a complexity class
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complexity: Option<ComplexityReport>,
+    // The comparisons counted, if the exercise declares a bound on them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comparisons: Option<ComparisonReport>,
+    // The error which kept the exercise from being compiled or run at all, if any
+    #[serde(skip)]
+    pub error: Option<Error>,
+    // The hash of the source the exercise was compiled from, taken before compiling it
+    #[serde(skip)]
+    pub source_hash: Option<String>,
+}
+
+impl ExerciseResult {
+    pub fn new(exercise: &Exercise, status: Status) -> ExerciseResult {
+        ExerciseResult {
+            name: exercise.name.clone(),
+            path: exercise.path.clone(),
+            mode: exercise.mode,
+            status,
+            exceeded: None,
+            expected: None,
+            compilation: None,
+            execution: None,
+            tests: Vec::new(),
+            cases: Vec::new(),
+            complexity: None,
+            comparisons: None,
+            error: None,
+            source_hash: None,
+        }
+    }
+
+    // Why the exercise didn't pass, if it didn't
+    pub fn error(&self) -> Option<Error> {
+        if self.passed() {
+            return None;
+        }
+        Some(match (&self.error, self.exceeded) {
+            (Some(error), _) => error.clone(),
+            (None, Some(Limit::Time(secs))) => Error::Timeout {
+                exercise: self.name.clone(),
+                secs,
+            },
+            (None, _) => Error::NotDone {
+                exercise: self.name.clone(),
+            },
+        })
+    }
+
+    // Whether the exercise passed, now or on a previous attempt
+    pub fn passed(&self) -> bool {
+        self.status == Status::Passed || self.status == Status::Cached
+    }
+
+    fn duration_secs(&self) -> f64 {
+        self.compilation
+            .iter()
+            .chain(&self.execution)
+            .map(|step| step.duration_secs)
+            .sum()
+    }
+
+    // The combined captured output of the execution, without color codes
+    fn output(&self) -> (String, String) {
+        let stdout = self.execution.as_ref().map_or("", |step| &step.stdout);
+        let stderr = self
+            .execution
+            .as_ref()
+            .or(self.compilation.as_ref())
+            .map_or("", |step| &step.stderr);
+        (
+            strip_ansi_codes(stdout).to_string(),
+            strip_ansi_codes(stderr).to_string(),
+        )
+    }
+
+    // A short description of why the exercise didn't pass
+    fn failure_message(&self) -> String {
+        match self.status {
+            Status::CompileError => self
+                .compilation
+                .iter()
+                .flat_map(|step| &step.diagnostics)
+                .find(|diagnostic| diagnostic.level == Level::Error)
+                .map_or_else(
+                    || String::from("compilation failed"),
+                    |diagnostic| match diagnostic.location() {
+                        Some(location) => format!("{} at {}", diagnostic.headline(), location),
+                        None => diagnostic.headline(),
+                    },
+                ),
+            Status::LimitExceeded => self
+                .exceeded
+                .map_or_else(|| String::from("limit exceeded"), |limit| limit.to_string()),
+            Status::WrongOutput => String::from("printed an unexpected output"),
+            Status::TooSlow => self.complexity.as_ref().map_or_else(
+                || String::from("too slow"),
+                |report| {
+                    format!(
+                        "grows like O({}) instead of O({})",
+                        report.fitted, report.declared
+                    )
+                },
+            ),
+            Status::TooManyComparisons => self.comparisons.as_ref().map_or_else(
+                || String::from("too many comparisons"),
+                |report| format!("more than {} comparisons", report.bound),
+            ),
+            Status::Unstable => String::from("the sort is not stable"),
+            Status::Counterexample => String::from("a generated input gave a wrong result"),
+            _ => String::from("execution failed"),
+        }
+    }
+}
+
+// Parse the test results printed by a libtest harness run with `--show-output`
+pub fn parse_tests(stdout: &str) -> Vec<TestCase> {
+    let mut tests: Vec<TestCase> = stdout
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("test ")?;
+            let (name, outcome) = rest.rsplit_once(" ... ")?;
+            let outcome = match outcome.trim() {
+                "ok" => TestOutcome::Ok,
+                "FAILED" => TestOutcome::Failed,
+                outcome if outcome.starts_with("ignored") => TestOutcome::Ignored,
+                _ => return None,
+            };
+            Some(TestCase {
+                name: name.to_string(),
+                outcome,
+                output: String::new(),
+            })
+        })
+        .collect();
+
+    // The captured output of each test is printed in a `---- name stdout ----` section
+    let mut current: Option<(&str, Vec<&str>)> = None;
+    let mut sections = Vec::new();
+    for line in stdout.lines() {
+        let header = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"));
+        let ends_section = header.is_some() || line == "successes:" || line == "failures:";
+        if ends_section {
+            if let Some(section) = current.take() {
+                sections.push(section);
+            }
+        }
+        match header {
+            Some(name) => current = Some((name, Vec::new())),
+            None => {
+                if let Some((_, lines)) = current.as_mut() {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    sections.extend(current);
+
+    for (name, lines) in sections {
+        if let Some(test) = tests.iter_mut().find(|test| test.name == name) {
+            test.output = lines.join("\n").trim_end().to_string();
+        }
+    }
+    tests
+}
+
+// Write the given results to a file in the given format
+pub fn write_report(
+    path: &Path,
+    format: ReportFormat,
+    results: &[ExerciseResult],
+) -> io::Result<()> {
+    let content = match format {
+        ReportFormat::Json => to_json(results),
+        ReportFormat::Junit => to_junit(results),
+        ReportFormat::Tap => to_tap(results),
+    };
+    fs::write(path, content)
+}
+
+#[derive(Serialize)]
+struct Summary {
+    total: usize,
+    passed: usize,
+    failed: usize,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    exercises: &'a [ExerciseResult],
+    summary: Summary,
+}
+
+fn to_json(results: &[ExerciseResult]) -> String {
+    let passed = results.iter().filter(|result| result.passed()).count();
+    let report = JsonReport {
+        exercises: results,
+        summary: Summary {
+            total: results.len(),
+            passed,
+            failed: results.len() - passed,
+        },
+    };
+    serde_json::to_string_pretty(&report).unwrap()
+}
+
+// Why a JUnit test case didn't pass
+enum Problem {
+    // An assertion failed
+    Failure(String, String),
+    // The test case could not be carried out
+    Error(String, String),
+}
+
+// A test case of a JUnit test suite
+struct JunitCase<'a> {
+    name: &'a str,
+    problem: Option<Problem>,
+    skipped: bool,
+}
+
+fn to_junit(results: &[ExerciseResult]) -> String {
+    let mut suites = String::new();
+    let (mut total_tests, mut total_failures, mut total_errors) = (0, 0, 0);
+    let mut total_time = 0.0;
+
+    for result in results {
+        let (stdout, stderr) = result.output();
+        let mut cases: Vec<JunitCase> = result
+            .tests
+            .iter()
+            .map(|test| JunitCase {
+                name: &test.name,
+                problem: match test.outcome {
+                    TestOutcome::Failed => Some(Problem::Failure(
+                        String::from("test failed"),
+                        test.output.clone(),
+                    )),
+                    _ => None,
+                },
+                skipped: test.outcome == TestOutcome::Ignored,
+            })
+            .chain(result.cases.iter().map(|case| JunitCase {
+                name: &case.name,
+                problem: match case.verdict {
+                    Verdict::Accepted => None,
+                    verdict => Some(Problem::Failure(
+                        format!("case {} failed with {}", case.name, verdict),
+                        String::new(),
+                    )),
+                },
+                skipped: false,
+            }))
+            .collect();
+
+        // A failure which no single test accounts for is reported on the exercise itself
+        let failure_reported = cases.iter().any(|case| case.problem.is_some());
+        if (!result.passed() && !failure_reported) || cases.is_empty() {
+            let problem = match result.status {
+                Status::Passed | Status::Cached => None,
+                Status::Failed
+                | Status::WrongOutput
+                | Status::TooSlow
+                | Status::TooManyComparisons
+                | Status::Unstable
+                | Status::Counterexample => Some(Problem::Failure(
+                    result.failure_message(),
+                    format!("{}{}", stdout, stderr),
+                )),
+                _ => Some(Problem::Error(result.failure_message(), stderr.clone())),
+            };
+            cases.push(JunitCase {
+                name: &result.name,
+                problem,
+                skipped: false,
+            });
+        }
+
+        let failures = cases
+            .iter()
+            .filter(|case| matches!(case.problem, Some(Problem::Failure(..))))
+            .count();
+        let errors = cases
+            .iter()
+            .filter(|case| matches!(case.problem, Some(Problem::Error(..))))
+            .count();
+        let skipped = cases.iter().filter(|case| case.skipped).count();
+        let time = result.duration_secs();
+        total_tests += cases.len();
+        total_failures += failures;
+        total_errors += errors;
+        total_time += time;
+
+        let _ = writeln!(
+            suites,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" errors="{}" skipped="{}" time="{:.3}">"#,
+            escape_xml(&result.name),
+            cases.len(),
+            failures,
+            errors,
+            skipped,
+            time
+        );
+        for case in cases {
+            let _ = write!(
+                suites,
+                r#"    <testcase classname="{}" name="{}""#,
+                escape_xml(&result.name),
+                escape_xml(case.name)
+            );
+            let (kind, message, output) = match case.problem {
+                Some(Problem::Failure(message, output)) => ("failure", message, output),
+                Some(Problem::Error(message, output)) => ("error", message, output),
+                None if case.skipped => {
+                    let _ = writeln!(suites, "><skipped/></testcase>");
+                    continue;
+                }
+                None => {
+                    let _ = writeln!(suites, "/>");
+                    continue;
+                }
+            };
+            let _ = writeln!(
+                suites,
+                r#"><{kind} message="{}">{}</{kind}></testcase>"#,
+                escape_xml(&message),
+                escape_xml(&output),
+                kind = kind
+            );
+        }
+        let _ = writeln!(
+            suites,
+            "    <system-out>{}</system-out>",
+            escape_xml(&stdout)
+        );
+        let _ = writeln!(
+            suites,
+            "    <system-err>{}</system-err>",
+            escape_xml(&stderr)
+        );
+        let _ = writeln!(suites, "  </testsuite>");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"algo\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n{}</testsuites>\n",
+        total_tests, total_failures, total_errors, total_time, suites
+    )
+}
+
+fn to_tap(results: &[ExerciseResult]) -> String {
+    let mut tap = String::from("TAP version 13\n");
+    let _ = writeln!(tap, "1..{}", results.len());
+
+    for (i, result) in results.iter().enumerate() {
+        match result.status {
+            Status::Passed => {
+                let _ = writeln!(tap, "ok {} - {}", i + 1, result.name);
+            }
+            Status::Cached => {
+                let _ = writeln!(
+                    tap,
+                    "ok {} - {} # SKIP unchanged since it last passed",
+                    i + 1,
+                    result.name
+                );
+            }
+            _ => {
+                let _ = writeln!(tap, "not ok {} - {}", i + 1, result.name);
+                let _ = writeln!(tap, "  ---");
+                let _ = writeln!(tap, "  message: {:?}", result.failure_message());
+                let _ = writeln!(tap, "  path: {:?}", result.path.to_string_lossy());
+                let failed: Vec<&TestCase> = result
+                    .tests
+                    .iter()
+                    .filter(|test| test.outcome == TestOutcome::Failed)
+                    .collect();
+                if !failed.is_empty() {
+                    let _ = writeln!(tap, "  failed_tests:");
+                    for test in failed {
+                        let _ = writeln!(tap, "    - {:?}", test.name);
+                    }
+                }
+                let _ = writeln!(tap, "  ...");
+            }
+        }
+    }
+    tap
+}
+
+// Escape the given text for XML, dropping the characters XML 1.0 doesn't allow
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HARNESS_OUTPUT: &str = "
+running 3 tests
+test tests::ignored ... ignored
+test tests::failing ... FAILED
+test tests::passing ... ok
+
+successes:
+
+---- tests::passing stdout ----
+sorted!
+
+
+successes:
+    tests::passing
+
+failures:
+
+---- tests::failing stdout ----
+thread 'tests::failing' panicked at src/main.rs:3:5:
+assertion failed
+
+
+failures:
+    tests::failing
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out
+";
+
+    #[test]
+    fn test_parse_tests() {
+        let tests = parse_tests(HARNESS_OUTPUT);
+        assert_eq!(
+            tests,
+            vec![
+                TestCase {
+                    name: "tests::ignored".into(),
+                    outcome: TestOutcome::Ignored,
+                    output: String::new(),
+                },
+                TestCase {
+                    name: "tests::failing".into(),
+                    outcome: TestOutcome::Failed,
+                    output:
+                        "thread 'tests::failing' panicked at src/main.rs:3:5:\nassertion failed"
+                            .into(),
+                },
+                TestCase {
+                    name: "tests::passing".into(),
+                    outcome: TestOutcome::Ok,
+                    output: "sorted!".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("a < b && \"c\"\u{1b}"),
+            "a &lt; b &amp;&amp; &quot;c&quot;"
+        );
+    }
+}
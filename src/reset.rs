@@ -0,0 +1,131 @@
+use crate::exercise::{has_marker, Exercise};
+use crate::progress::{now, Progress};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+include!(concat!(env!("OUT_DIR"), "/pristine.rs"));
+
+const I_AM_NOT_DONE: &str = "// I AM NOT DONE";
+const BACKUP_DIR: &str = ".algo/backups";
+
+// Restore the given exercise to its pristine stub and forget its completion.
+// A file which has been modified is only overwritten if force is set,
+// in which case the learner's version is backed up first.
+pub fn reset(exercise: &Exercise, progress: &mut Progress, force: bool) -> Result<(), ()> {
+    let (embedded, pristine) = match embedded_source(exercise) {
+        Some(embedded) => (embedded, with_marker(embedded)),
+        None => {
+            warn!("There is no pristine copy of {} to restore", exercise);
+            return Err(());
+        }
+    };
+
+    match fs::read_to_string(&exercise.path) {
+        Ok(ref current) if *current == pristine => {}
+        Ok(ref current) if current == embedded => restore(exercise, &pristine)?,
+        Ok(current) => {
+            if !force {
+                warn!("{} has been modified!", exercise);
+                println!("Run the command again with `--force` to discard your changes.");
+                return Err(());
+            }
+            match backup(exercise, &current) {
+                Ok(backup_path) => println!("Your version was saved to {}", backup_path.display()),
+                Err(e) => {
+                    warn!("Could not back up your version: {}", e);
+                    return Err(());
+                }
+            }
+            restore(exercise, &pristine)?;
+        }
+        Err(_) => restore(exercise, &pristine)?,
+    }
+
+    progress.reset(exercise);
+    if let Err(e) = progress.save() {
+        warn!("Could not save your progress: {}", e);
+    }
+    success!("Successfully reset {}", exercise);
+    Ok(())
+}
+
+// Get the stub of the given exercise as it was embedded at build time
+fn embedded_source(exercise: &Exercise) -> Option<&'static str> {
+    let key = exercise.path.to_string_lossy().replace('\\', "/");
+    let key = key.trim_start_matches("./");
+    PRISTINE_EXERCISES
+        .iter()
+        .find(|(path, _)| *path == key)
+        .map(|(_, source)| *source)
+}
+
+// Insert the `I AM NOT DONE` marker right after the leading description comment
+fn with_marker(source: &str) -> String {
+    if has_marker(source) {
+        return source.to_string();
+    }
+
+    let header_end = if source.trim_start().starts_with("/*") {
+        source.find("*/").map_or(0, |end| end + "*/".len())
+    } else {
+        0
+    };
+    let (header, body) = source.split_at(header_end);
+    let body = body.trim_start_matches(['\r', '\n']);
+    if header.is_empty() {
+        format!("{}\n\n{}", I_AM_NOT_DONE, body)
+    } else {
+        format!("{}\n\n{}\n\n{}", header, I_AM_NOT_DONE, body)
+    }
+}
+
+fn restore(exercise: &Exercise, pristine: &str) -> Result<(), ()> {
+    let written = match exercise.path.parent() {
+        Some(parent) if parent != Path::new("") => fs::create_dir_all(parent),
+        _ => Ok(()),
+    }
+    .and_then(|_| fs::write(&exercise.path, pristine));
+
+    written.map_err(|e| {
+        warn!("Could not restore the exercise: {}", e);
+    })
+}
+
+fn backup(exercise: &Exercise, current: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(BACKUP_DIR)?;
+    let backup_path = Path::new(BACKUP_DIR).join(format!("{}.{}.rs", exercise.name, now()));
+    fs::write(&backup_path, current)?;
+    Ok(backup_path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+
+    #[test]
+    fn test_embedded_source_with_marker() {
+        let exercise = Exercise {
+            name: "heap_sort".into(),
+            path: PathBuf::from("./fundamentals/algorithms/sorts/heap_sort.rs"),
+            mode: Mode::Test,
+            ..Default::default()
+        };
+        let source = with_marker(embedded_source(&exercise).unwrap());
+        assert!(has_marker(&source));
+        assert!(source.starts_with("/*"));
+        assert!(source.contains("*/\n\n// I AM NOT DONE\n\nfn heap_sort"));
+    }
+
+    #[test]
+    fn test_no_embedded_source() {
+        let exercise = Exercise {
+            name: "pending_exercise".into(),
+            path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
+            mode: Mode::Compile,
+            ..Default::default()
+        };
+        assert_eq!(embedded_source(&exercise), None);
+    }
+}
@@ -1,55 +1,106 @@
+use crate::error::Error;
 use crate::exercise::{Exercise, Mode};
-use crate::verify::test;
-use indicatif::ProgressBar;
+use crate::progress::Progress;
+use crate::report::{ExerciseResult, Status};
+use crate::verify::{
+    execute_with_spinner, print_cases, print_comparisons, print_compilation_failure,
+    print_complexity, print_harness_failure, print_output_mismatch, report, warn_limit_exceeded,
+};
 
 // Invoke the rust compiler on the path of the given exercise,
 // and run the ensuing binary.
 // The verbose argument helps determine whether or not to show
 // the output from the test harnesses (if the mode of the exercise is test)
-pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    match exercise.mode {
-        Mode::Test => test(exercise, verbose)?,
-        Mode::Compile => compile_and_run(exercise)?,
-        Mode::Clippy => compile_and_run(exercise)?,
-    }
-    Ok(())
+// The outcome is recorded in the given progress, and the structured
+// result is added to results. The error returns why the exercise didn't pass.
+pub fn run(
+    exercise: &Exercise,
+    progress: &mut Progress,
+    results: &mut Vec<ExerciseResult>,
+    verbose: bool,
+) -> Result<(), Error> {
+    let result = execute_with_spinner(exercise, true);
+    let reported = match exercise.mode {
+        Mode::Test => report(exercise, &result, verbose).map(|_| ()),
+        Mode::Compile | Mode::Output => report_run(exercise, &result),
+        Mode::Clippy => report_run(exercise, &result),
+    };
+    progress.record_and_save(exercise, reported.is_ok(), result.source_hash.clone());
+    let error = result.error();
+    results.push(result);
+    reported.map_err(|_| {
+        error.unwrap_or_else(|| Error::NotDone {
+            exercise: exercise.name.clone(),
+        })
+    })
 }
 
-// Invoke the rust compiler on the path of the given exercise
-// and run the ensuing binary.
+// Report the result of running the given exercise.
 // This is strictly for non-test binaries, so output is displayed
-fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {}...", exercise).as_str());
-    progress_bar.enable_steady_tick(100);
+fn report_run(exercise: &Exercise, result: &ExerciseResult) -> Result<(), ()> {
+    let stdout = result.execution.as_ref().map_or("", |step| &step.stdout);
+    let stderr = result.execution.as_ref().map_or("", |step| &step.stderr);
 
-    let compilation_result = exercise.compile();
-    let compilation = match compilation_result {
-        Ok(compilation) => compilation,
-        Err(output) => {
-            progress_bar.finish_and_clear();
+    if !result.cases.is_empty() {
+        print_cases(&result.cases);
+    }
+    if let Some(complexity) = &result.complexity {
+        print_complexity(complexity, true);
+    }
+    if let Some(comparisons) = &result.comparisons {
+        print_comparisons(comparisons);
+    }
+    match result.status {
+        Status::Passed | Status::Cached => {
+            println!("{}", stdout);
+            success!("Successfully ran {}", exercise);
+            Ok(())
+        }
+        Status::CompileError => {
             warn!(
                 "Compilation of {} failed!, Compiler error message:\n",
                 exercise
             );
-            println!("{}", output.stderr);
-            return Err(());
+            print_compilation_failure(result);
+            Err(())
         }
-    };
-
-    progress_bar.set_message(format!("Running {}...", exercise).as_str());
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
-
-    match result {
-        Ok(output) => {
-            println!("{}", output.stdout);
-            success!("Successfully ran {}", exercise);
-            Ok(())
+        Status::LimitExceeded => {
+            println!("{}", stdout);
+            if let Some(limit) = result.exceeded {
+                warn_limit_exceeded(exercise, limit);
+            }
+            Err(())
+        }
+        Status::WrongOutput => {
+            warn!("Ran {} without the expected output", exercise);
+            print_output_mismatch(exercise, result);
+            Err(())
+        }
+        Status::TooSlow => {
+            println!("{}", stdout);
+            warn!("Ran {} too slowly for its declared complexity", exercise);
+            Err(())
+        }
+        Status::TooManyComparisons => {
+            println!("{}", stdout);
+            warn!("Ran {} with too many comparisons", exercise);
+            Err(())
+        }
+        Status::Unstable => {
+            println!("{}", stdout);
+            warn!("Ran {} with an unstable sort", exercise);
+            print_harness_failure(result);
+            Err(())
+        }
+        Status::Counterexample => {
+            println!("{}", stdout);
+            warn!("Ran {} with a counterexample", exercise);
+            print_harness_failure(result);
+            Err(())
         }
-        Err(output) => {
-            println!("{}", output.stdout);
-            println!("{}", output.stderr);
+        Status::Failed => {
+            println!("{}", stdout);
+            println!("{}", stderr);
 
             warn!("Ran {} with errors", exercise);
             Err(())
@@ -0,0 +1,198 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::{Mutex, MutexGuard};
+
+// Where the scratch directories are created when XDG_CACHE_HOME isn't set,
+// relative to the directory algo is run from
+const TARGET_SCRATCH_ROOT: &str = "target/algo";
+// The prefix of the scratch directory of every run, followed by its process id
+const RUN_PREFIX: &str = "run-";
+
+// The scratch directory of the current run, if it still exists
+static CURRENT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// The directory every exercise of this run is compiled in. It is removed
+// along with everything in it when the guard is dropped, when the run is
+// interrupted with Ctrl-C, or when the program exits through `exit`
+pub struct ScratchDir {
+    _path: PathBuf,
+}
+
+impl ScratchDir {
+    // Create the scratch directory of this run, and remove the ones
+    // left behind by runs which were killed
+    pub fn create() -> io::Result<ScratchDir> {
+        let root = scratch_root();
+        remove_stale(&root);
+        let path = root.join(format!("{}{}", RUN_PREFIX, process::id()));
+        fs::create_dir_all(&path)?;
+        *current() = Some(path.clone());
+        remove_on_interrupt();
+        Ok(ScratchDir { _path: path })
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        remove();
+    }
+}
+
+// The scratch directory of the current run. Runs without a guard, such as
+// the unit tests, get one on first use which is only removed by the next run
+pub fn scratch_dir() -> PathBuf {
+    let mut current = current();
+    match &*current {
+        Some(path) => path.clone(),
+        None => {
+            let path = scratch_root().join(format!("{}{}", RUN_PREFIX, process::id()));
+            let _ignored = fs::create_dir_all(&path);
+            *current = Some(path.clone());
+            path
+        }
+    }
+}
+
+// Remove the scratch directory of the current run, if there is one
+pub fn remove() {
+    if let Some(path) = current().take() {
+        let _ignored = fs::remove_dir_all(&path);
+        // The directory of every run only goes away with the last one
+        if let Some(root) = path.parent() {
+            let _ignored = fs::remove_dir(root);
+        }
+    }
+}
+
+// Remove the scratch directory and exit with the given code.
+// Exiting directly would skip the guard
+pub fn exit(code: i32) -> ! {
+    remove();
+    process::exit(code)
+}
+
+fn current() -> MutexGuard<'static, Option<PathBuf>> {
+    CURRENT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// The directory holding the scratch directory of every run, in the
+// cache directory of the user if there is one, or in the target directory
+fn scratch_root() -> PathBuf {
+    match env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(cache) => PathBuf::from(cache).join("rust-cs-fundamentals"),
+        None => PathBuf::from(TARGET_SCRATCH_ROOT),
+    }
+}
+
+// Remove the scratch directories of the runs which aren't running anymore
+fn remove_stale(root: &Path) {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        let pid = path
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix(RUN_PREFIX)?.parse().ok());
+        if pid.is_some_and(|pid| pid != process::id() && !is_running(pid)) {
+            let _ignored = fs::remove_dir_all(path);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    use std::convert::TryFrom;
+
+    let pid = match libc::pid_t::try_from(pid) {
+        Ok(pid) if pid > 0 => pid,
+        _ => return false,
+    };
+    // SAFETY: the null signal only checks whether the process exists
+    let alive = unsafe { libc::kill(pid, 0) } == 0;
+    alive || io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH)
+}
+
+#[cfg(not(unix))]
+fn is_running(_pid: u32) -> bool {
+    true
+}
+
+// Remove the scratch directory when the run is interrupted with Ctrl-C.
+// The signal handler only writes to a pipe, a thread waiting on the
+// other end removes the directory and exits
+#[cfg(unix)]
+fn remove_on_interrupt() {
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::sync::Once;
+    use std::thread;
+
+    static PIPE: AtomicI32 = AtomicI32::new(-1);
+    static INSTALL: Once = Once::new();
+
+    extern "C" fn on_interrupt(_signal: libc::c_int) {
+        let byte = 0u8;
+        // SAFETY: write is async-signal-safe, and the pipe is never closed
+        unsafe {
+            libc::write(
+                PIPE.load(Ordering::SeqCst),
+                &byte as *const u8 as *const _,
+                1,
+            );
+        }
+    }
+
+    INSTALL.call_once(|| {
+        let mut fds = [0; 2];
+        // SAFETY: fds has room for both ends of the pipe
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return;
+        }
+        PIPE.store(fds[1], Ordering::SeqCst);
+        thread::spawn(move || {
+            let mut byte = 0u8;
+            // SAFETY: byte is a valid buffer of one byte
+            if unsafe { libc::read(fds[0], &mut byte as *mut u8 as *mut _, 1) } == 1 {
+                exit(130);
+            }
+        });
+        // SAFETY: the handler only calls async-signal-safe functions
+        unsafe {
+            libc::signal(
+                libc::SIGINT,
+                on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            );
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn remove_on_interrupt() {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_remove_stale() {
+        let root = env::temp_dir().join(format!("algo_scratch_{}", process::id()));
+        // No process can have such a large id, and the current one is running
+        let stale = root.join(format!("{}{}", RUN_PREFIX, i32::MAX));
+        let running = root.join(format!("{}{}", RUN_PREFIX, process::id()));
+        let unrelated = root.join("unrelated");
+        for dir in &[&stale, &running, &unrelated] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        remove_stale(&root);
+        assert!(!stale.exists());
+        assert!(running.exists());
+        assert!(unrelated.exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
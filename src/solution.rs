@@ -0,0 +1,51 @@
+use crate::diff::{diff_lines, print_side_by_side};
+use crate::exercise::Exercise;
+use crate::progress::Progress;
+use console::Term;
+use std::fs;
+
+// Show the reference solution of the given exercise.
+// The solution is only revealed once the exercise has been solved,
+// unless force is set. If diff is set, the solution is shown
+// side by side with the learner's version of the exercise.
+pub fn solution(
+    exercise: &Exercise,
+    progress: &Progress,
+    force: bool,
+    diff: bool,
+) -> Result<(), ()> {
+    let solution_path = match &exercise.solution {
+        Some(solution_path) => solution_path,
+        None => {
+            warn!("There is no reference solution for {}", exercise);
+            return Err(());
+        }
+    };
+
+    if !force && !progress.is_solved(exercise) {
+        warn!("{} is not done yet!", exercise);
+        println!("Solve it first, or run the command again with `--force` to reveal the solution anyway.");
+        return Err(());
+    }
+
+    let solution = fs::read_to_string(solution_path).map_err(|e| {
+        warn!("Could not read the reference solution: {}", e);
+    })?;
+
+    if !diff {
+        println!("{}", solution);
+        return Ok(());
+    }
+
+    let current = fs::read_to_string(&exercise.path).map_err(|e| {
+        warn!("Could not read the exercise: {}", e);
+    })?;
+    let (_, width) = Term::stdout().size();
+    print_side_by_side(
+        &diff_lines(&current, &solution),
+        &exercise.path.to_string_lossy(),
+        &solution_path.to_string_lossy(),
+        width as usize,
+    );
+    Ok(())
+}
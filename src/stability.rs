@@ -0,0 +1,53 @@
+use crate::harness::INPUT_SOURCE;
+
+// The name of the test which checks that the sort of an exercise is stable
+pub const HARNESS_TEST: &str = "__algo_stability::stable";
+// The `Keyed` records which are sorted
+const STABILITY_SOURCE: &str = include_str!("../harness/stability.rs");
+// The number of records sorted, and the number of distinct keys among them
+const SIZE: usize = 256;
+const KEYS: usize = 16;
+
+// The source of a test harness checking that the given expression sorts stably,
+// to append to the source of the exercise. The expression is evaluated with
+// a `Vec<Keyed>` named `input` holding many records with the same keys,
+// see `harness/stability.rs`. The test fails with the first records
+// with equal keys which were reordered
+pub fn harness(call: &str) -> String {
+    format!(
+        r#"
+#[cfg(test)]
+mod __algo_stability {{
+    #[allow(unused_imports)]
+    use super::*;
+
+{stability_source}
+{input_source}
+    #[test]
+    fn stable() {{
+        #[allow(unused_mut)]
+        let mut input: Vec<Keyed> = input({size}, false)
+            .into_iter()
+            .enumerate()
+            .map(|(index, n)| Keyed {{ key: n % {keys}, index }})
+            .collect();
+        #[allow(unused_variables)]
+        let item = Keyed {{ key: -1, index: 0 }};
+        {call};
+        if let Some((first, second)) = first_unstable(&input) {{
+            panic!(
+                "The sort is not stable: of the records with key {{}}, the one at index {{}} \
+                 now comes before the one at index {{}}",
+                first.key, first.index, second.index
+            );
+        }}
+    }}
+}}
+"#,
+        stability_source = STABILITY_SOURCE,
+        input_source = INPUT_SOURCE,
+        size = SIZE,
+        keys = KEYS,
+        call = call,
+    )
+}
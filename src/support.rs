@@ -0,0 +1,101 @@
+use crate::check::collect_sources;
+use crate::diagnostic::parse_diagnostics;
+use crate::exercise::{fnv1a, toolchain_version, ExerciseOutput, EDITION};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+// The crate of helpers shared by every exercise, and its root module
+pub const SUPPORT_ROOT: &str = "fundamentals/support";
+pub const SUPPORT_LIB: &str = "fundamentals/support/lib.rs";
+// Where the support library is built, along with the version it was built from
+const SUPPORT_BUILD_DIR: &str = ".algo/support";
+const SUPPORT_RLIB: &str = "libsupport.rlib";
+const SUPPORT_VERSION_FILE: &str = "version";
+
+// The version of the support library, a hash of its sources.
+// None if there is no support library
+pub fn support_version() -> Option<String> {
+    static VERSION: OnceLock<Option<String>> = OnceLock::new();
+    VERSION
+        .get_or_init(|| {
+            if !Path::new(SUPPORT_LIB).exists() {
+                return None;
+            }
+            let mut sources = Vec::new();
+            collect_sources(Path::new(SUPPORT_ROOT), &mut sources).ok()?;
+            sources.sort();
+            let mut bytes = Vec::new();
+            for source in sources {
+                bytes.extend(source.to_string_lossy().as_bytes());
+                bytes.extend(fs::read(&source).ok()?);
+            }
+            Some(format!("{:016x}", fnv1a(&bytes)))
+        })
+        .clone()
+}
+
+// The fingerprint of what an exercise is built with besides its own source:
+// the compiler, and the version of the support library if there is one
+pub fn build_fingerprint() -> String {
+    match support_version() {
+        Some(version) => format!("{} + support {}", toolchain_version(), version),
+        None => toolchain_version(),
+    }
+}
+
+// The arguments linking an exercise with the support library, built once per run.
+// It is only rebuilt if its sources or the compiler changed since the last build.
+// There are no arguments if there is no support library
+pub fn support_args() -> Result<Vec<String>, ExerciseOutput> {
+    static BUILT: OnceLock<Result<Option<PathBuf>, ExerciseOutput>> = OnceLock::new();
+    let built = BUILT.get_or_init(|| match support_version() {
+        Some(version) => build(&version).map(Some),
+        None => Ok(None),
+    });
+    match built {
+        Ok(Some(rlib)) => Ok(vec![
+            String::from("--extern"),
+            format!("support={}", rlib.display()),
+            String::from("-L"),
+            format!("dependency={}", SUPPORT_BUILD_DIR),
+        ]),
+        Ok(None) => Ok(Vec::new()),
+        Err(output) => Err(output.clone()),
+    }
+}
+
+// Build the support library, unless the build of the given version is still there
+fn build(version: &str) -> Result<PathBuf, ExerciseOutput> {
+    let dir = Path::new(SUPPORT_BUILD_DIR);
+    let rlib = dir.join(SUPPORT_RLIB);
+    let fingerprint = format!("{} {}", version, toolchain_version());
+    let version_file = dir.join(SUPPORT_VERSION_FILE);
+    if rlib.exists() && fs::read_to_string(&version_file).ok().as_deref() == Some(&fingerprint) {
+        return Ok(rlib);
+    }
+
+    let failure =
+        |e: io::Error| ExerciseOutput::error(format!("Could not build the support library: {}", e));
+    fs::create_dir_all(dir).map_err(failure)?;
+    let cmd = Command::new("rustc")
+        .args(["--crate-type", "rlib", "--crate-name", "support", "-O"])
+        .args(["--edition", EDITION, "--error-format=json"])
+        .arg(SUPPORT_LIB)
+        .arg("-o")
+        .arg(&rlib)
+        .output()
+        .map_err(|e| ExerciseOutput::spawn_failure("rustc", e))?;
+    if !cmd.status.success() {
+        let (diagnostics, rendered) = parse_diagnostics(&String::from_utf8_lossy(&cmd.stderr));
+        return Err(ExerciseOutput {
+            stderr: format!("Could not build the support library:\n{}", rendered),
+            diagnostics: diagnostics.into(),
+            ..ExerciseOutput::default()
+        });
+    }
+    fs::write(&version_file, fingerprint).map_err(failure)?;
+    Ok(rlib)
+}
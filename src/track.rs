@@ -0,0 +1,184 @@
+use crate::error::Error;
+use crate::exercise::ExerciseList;
+use crate::progress::{Progress, PROGRESS_FILE_PATH};
+use console::style;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// The name of the track whose manifest is info.toml
+pub const DEFAULT_TRACK: &str = "default";
+const DEFAULT_MANIFEST: &str = "info.toml";
+// The directory holding the manifests of the other tracks, one `<name>.toml` per track
+pub const TRACKS_DIR: &str = "tracks";
+// Where the track chosen last with `--track` is remembered
+const CURRENT_TRACK_FILE: &str = ".algo/track";
+// The directory holding the progress of every track but the default one
+const TRACKS_PROGRESS_DIR: &str = ".algo/tracks";
+
+// A course made of the exercises listed in its manifest
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+    pub name: String,
+    // The exercise list of the track, such as `tracks/data_structures.toml`
+    pub manifest: PathBuf,
+}
+
+impl Track {
+    // The track with the given name, if its manifest exists
+    pub fn named(name: &str) -> Option<Track> {
+        let manifest = if name == DEFAULT_TRACK {
+            PathBuf::from(DEFAULT_MANIFEST)
+        } else if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            Path::new(TRACKS_DIR).join(format!("{}.toml", name))
+        } else {
+            return None;
+        };
+        if !manifest.is_file() {
+            return None;
+        }
+        Some(Track {
+            name: name.to_string(),
+            manifest,
+        })
+    }
+
+    // Every track, the default one first and the others by name
+    pub fn all() -> Vec<Track> {
+        let mut names: Vec<String> = fs::read_dir(TRACKS_DIR)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|e| e == "toml"))
+                    .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        Some(String::from(DEFAULT_TRACK))
+            .into_iter()
+            .chain(names)
+            .filter_map(|name| Track::named(&name))
+            .collect()
+    }
+
+    // The track chosen last with `--track`, or else the first track
+    pub fn current() -> Option<Track> {
+        fs::read_to_string(CURRENT_TRACK_FILE)
+            .ok()
+            .and_then(|name| Track::named(name.trim()))
+            .or_else(|| Track::all().into_iter().next())
+    }
+
+    // Remember this track as the one to work on until another one is chosen
+    pub fn select(&self) -> io::Result<()> {
+        if let Some(parent) = Path::new(CURRENT_TRACK_FILE).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(CURRENT_TRACK_FILE, &self.name)
+    }
+
+    // The file the progress on this track is saved to. The default track
+    // keeps the progress file it had before there were other tracks
+    pub fn progress_path(&self) -> PathBuf {
+        if self.name == DEFAULT_TRACK {
+            PathBuf::from(PROGRESS_FILE_PATH)
+        } else {
+            Path::new(TRACKS_PROGRESS_DIR)
+                .join(&self.name)
+                .join("progress.toml")
+        }
+    }
+
+    pub fn load(&self) -> Result<ExerciseList, Error> {
+        ExerciseList::load(&self.manifest)
+    }
+}
+
+// Print every track along with its title and how many of its exercises
+// are done, marking the current one
+pub fn tracks(current: &Track) -> Result<(), ()> {
+    let tracks = Track::all();
+    let name_width = tracks
+        .iter()
+        .map(|track| track.name.len())
+        .chain(Some("Track".len()))
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "  {:name_width$}  Progress  Title",
+        "Track",
+        name_width = name_width
+    );
+    let mut failed = false;
+    for track in &tracks {
+        let marker = if track == current { "*" } else { " " };
+        let list = match track.load() {
+            Ok(list) => list,
+            Err(e) => {
+                warn!("Could not load the track: {}", e);
+                failed = true;
+                continue;
+            }
+        };
+        let progress_path = track.progress_path();
+        let progress = match Progress::load(&progress_path) {
+            Ok(progress) => progress,
+            Err(e) => {
+                warn!("Could not read your progress on the {} track", track.name);
+                println!("{}: {}", progress_path.display(), e);
+                println!("Fix or remove the file to start over.");
+                failed = true;
+                continue;
+            }
+        };
+        let done = list
+            .exercises
+            .iter()
+            .filter(|e| e.is_done(&progress))
+            .count();
+        let completed = format!("{} / {}", done, list.exercises.len());
+        let name = format!("{:name_width$}", track.name, name_width = name_width);
+        let name = if track == current {
+            style(name).bold()
+        } else {
+            style(name)
+        };
+        println!(
+            "{} {}  {:8}  {}",
+            marker,
+            name,
+            completed,
+            list.title.as_deref().unwrap_or("")
+        );
+    }
+    println!();
+    println!("Run `algo --track <name>` to switch to another track.");
+
+    if failed {
+        Err(())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_named() {
+        assert!(Track::named("../info").is_none());
+        assert!(Track::named("").is_none());
+        assert!(Track::named("missing").is_none());
+        let default = Track::named(DEFAULT_TRACK).unwrap();
+        assert_eq!(default.manifest, Path::new("info.toml"));
+        assert_eq!(default.progress_path(), Path::new(PROGRESS_FILE_PATH));
+    }
+}
@@ -1,144 +1,520 @@
-use crate::exercise::{CompiledExercise, Exercise, Mode, State};
+use crate::comparisons::ComparisonReport;
+use crate::complexity::{chart, ComplexityReport};
+use crate::diagnostic::print_diagnostics;
+use crate::diff::{diff_lines, print_unified};
+use crate::error::Error;
+use crate::exercise::{CaseResult, Exercise, ExerciseOutput, Limit, Mode, State, Verdict};
+use crate::harness::panic_message;
+use crate::progress::Progress;
+use crate::report::{parse_tests, ExerciseResult, Status, Step};
 use console::style;
 use indicatif::ProgressBar;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Every job compiling or running an exercise shares this lock, while measuring
+// the running time of an exercise takes it for itself, so that the jobs
+// running `rustc` in parallel don't slow the measured exercise down
+static TIMING: RwLock<()> = RwLock::new(());
+
+// A job which panicked while holding the lock didn't leave anything behind it
+fn shared_timing() -> RwLockReadGuard<'static, ()> {
+    TIMING.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn exclusive_timing() -> RwLockWriteGuard<'static, ()> {
+    TIMING.write().unwrap_or_else(|e| e.into_inner())
+}
 
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
 // Any such failures will be reported to the end user.
 // If the Exercise being verified is a test, the verbose boolean
 // determines whether or not the test harness outputs are displayed.
+// The outcome of every attempt is recorded in the given progress,
+// and the structured result of every verified exercise is added to results.
+// Exercises which are unchanged since they last passed are not compiled again.
+// With more than one job, the exercises are compiled and run in parallel,
+// but they are still reported in order up to the first failing one,
+// whose error is returned.
 pub fn verify<'a>(
     start_at: impl IntoIterator<Item = &'a Exercise>,
+    progress: &mut Progress,
+    results: &mut Vec<ExerciseResult>,
     verbose: bool,
-) -> Result<(), &'a Exercise> {
-    for exercise in start_at {
-        let compile_result = match exercise.mode {
-            Mode::Test => compile_and_test(&exercise, RunMode::Interactive, verbose),
-            Mode::Compile => compile_and_run_interactively(&exercise),
-            Mode::Clippy => compile_only(&exercise),
-        };
-        if !compile_result.unwrap_or(false) {
-            return Err(exercise);
-        }
+    jobs: usize,
+) -> Result<(), Error> {
+    let exercises: Vec<&Exercise> = start_at.into_iter().collect();
+    let cached: Vec<bool> = exercises.iter().map(|e| progress.is_cached(e)).collect();
+
+    if jobs <= 1 {
+        return verify_in_order(&exercises, &cached, progress, results, verbose, |i| {
+            execute_with_spinner(exercises[i], false)
+        });
     }
-    Ok(())
-}
 
-enum RunMode {
-    Interactive,
-    NonInteractive,
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = channel();
+    let mut received: Vec<Option<ExerciseResult>> = exercises.iter().map(|_| None).collect();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                let sender = sender.clone();
+                let (next, stop, exercises, cached) = (&next, &stop, &exercises, &cached);
+                scope.spawn(move || loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    if i >= exercises.len() || stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if cached[i] {
+                        continue;
+                    }
+                    let _ = sender.send((i, execute(exercises[i], &ProgressBar::hidden(), false)));
+                })
+            })
+            .collect();
+        // Only the workers can send, so receiving fails once they are all gone
+        drop(sender);
+
+        let result = verify_in_order(&exercises, &cached, progress, results, verbose, |i| {
+            let progress_bar = spinner(exercises[i]);
+            while received[i].is_none() {
+                match receiver.recv() {
+                    Ok((index, result)) => received[index] = Some(result),
+                    Err(_) => break,
+                }
+            }
+            // The exercise is executed here if the worker which took it panicked
+            let result = received[i]
+                .take()
+                .unwrap_or_else(|| execute(exercises[i], &progress_bar, false));
+            progress_bar.finish_and_clear();
+            result
+        });
+        stop.store(true, Ordering::SeqCst);
+        // The panic of a worker has been made up for, it doesn't fail the run
+        for worker in workers {
+            let _ = worker.join();
+        }
+        result
+    })
 }
 
-// Compile and run the resulting test harness of the given Exercise
-pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    compile_and_test(exercise, RunMode::NonInteractive, verbose)?;
+// Report the results of the given exercises in order, stopping at the first
+// one which fails or is not done yet.
+// The result of an exercise which isn't cached is obtained from its index.
+fn verify_in_order(
+    exercises: &[&Exercise],
+    cached: &[bool],
+    progress: &mut Progress,
+    results: &mut Vec<ExerciseResult>,
+    verbose: bool,
+    mut result_of: impl FnMut(usize) -> ExerciseResult,
+) -> Result<(), Error> {
+    for (i, exercise) in exercises.iter().enumerate() {
+        let result = if cached[i] {
+            ExerciseResult::new(exercise, Status::Cached)
+        } else {
+            result_of(i)
+        };
+        let reported = report(exercise, &result, verbose);
+        if !cached[i] {
+            progress.record_and_save(exercise, reported.is_ok(), result.source_hash.clone());
+        }
+        let error = result.error();
+        results.push(result);
+
+        let done = match reported {
+            Ok(output) => prompt_for_completion(exercise, output, progress),
+            Err(_) => false,
+        };
+        if !done {
+            return Err(error.unwrap_or_else(|| Error::NotDone {
+                exercise: exercise.name.clone(),
+            }));
+        }
+    }
     Ok(())
 }
 
-// Invoke the rust compiler without running the resulting binary
-fn compile_only(exercise: &Exercise) -> Result<bool, ()> {
+fn spinner(exercise: &Exercise) -> ProgressBar {
     let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {}...", exercise).as_str());
+    let message = match exercise.mode {
+        Mode::Test => format!("Testing {}...", exercise),
+        _ => format!("Compiling {}...", exercise),
+    };
+    progress_bar.set_message(message.as_str());
     progress_bar.enable_steady_tick(100);
+    progress_bar
+}
 
-    let _ = compile(&exercise, &progress_bar)?;
+// Compile and run the given Exercise while showing a spinner
+pub fn execute_with_spinner(exercise: &Exercise, run_clippy: bool) -> ExerciseResult {
+    let progress_bar = spinner(exercise);
+    let result = execute(exercise, &progress_bar, run_clippy);
     progress_bar.finish_and_clear();
-
-    success!("Successfully compiled {}!", exercise);
-    Ok(prompt_for_completion(&exercise, None))
+    result
 }
 
-// Compile the given Exercise and run the resulting binary in an interactive mode
-fn compile_and_run_interactively(exercise: &Exercise) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {}...", exercise).as_str());
-    progress_bar.enable_steady_tick(100);
-
-    let compilation = compile(&exercise, &progress_bar)?;
-
-    progress_bar.set_message(format!("Running {}...", exercise).as_str());
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
+// Compile the given Exercise and run the resulting binary.
+// The binary of an exercise linted with clippy is only run if run_clippy is set
+fn execute(exercise: &Exercise, progress_bar: &ProgressBar, run_clippy: bool) -> ExerciseResult {
+    // The source may be saved again while it compiles, only this version is tested
+    let source_hash = exercise.source_hash().ok();
+    let shared = shared_timing();
+    let start = Instant::now();
+    let compilation = exercise.compile();
+    let compile_time = start.elapsed();
 
-    let output = match result {
-        Ok(output) => output,
+    let compilation = match compilation {
+        Ok(compilation) => compilation,
         Err(output) => {
-            warn!("Ran {} with errors", exercise);
-            println!("{}", output.stdout);
-            println!("{}", output.stderr);
-            return Err(());
+            let mut result = ExerciseResult::new(exercise, Status::CompileError);
+            result.source_hash = source_hash;
+            result.error = output.error.clone();
+            result.compilation = Some(Step::new(false, compile_time, output));
+            return result;
         }
     };
+    let mut result = ExerciseResult::new(exercise, Status::Passed);
+    result.source_hash = source_hash;
+    result.compilation = Some(Step::new(true, compile_time, ExerciseOutput::default()));
+    if exercise.mode == Mode::Clippy && !run_clippy {
+        return result;
+    }
 
-    success!("Successfully ran {}!", exercise);
+    if exercise.mode != Mode::Test {
+        progress_bar.set_message(format!("Running {}...", exercise).as_str());
+    }
+    let start = Instant::now();
+    let execution = compilation.run();
+    let run_time = start.elapsed();
 
-    Ok(prompt_for_completion(&exercise, Some(output.stdout)))
+    let (success, output) = match execution {
+        Ok(output) => (true, output),
+        Err(output) => (false, output),
+    };
+    result.status = match output.exceeded {
+        _ if success => Status::Passed,
+        Some(_) => Status::LimitExceeded,
+        None if output.expected.is_some() => Status::WrongOutput,
+        None => Status::Failed,
+    };
+    result.exceeded = output.exceeded;
+    result.expected = output.expected.clone();
+    result.cases = output.cases.to_vec();
+    result.error = output.error.clone();
+    if exercise.mode == Mode::Test {
+        result.tests = parse_tests(&output.stdout);
+    }
+    result.execution = Some(Step::new(success, run_time, output));
+    drop(shared);
+    if success {
+        run_harnesses(exercise, progress_bar, &mut result);
+    }
+    result
 }
 
-// Compile the given Exercise as a test harness and display
-// the output if verbose is set to true
-fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Testing {}...", exercise).as_str());
-    progress_bar.enable_steady_tick(100);
+// Check the given Exercise against a reference implementation and for stability,
+// measure how its running time grows and count its comparisons,
+// as far as it declares to. A harness which fails replaces the execution step
+fn run_harnesses(exercise: &Exercise, progress_bar: &ProgressBar, result: &mut ExerciseResult) {
+    progress_bar.set_message(format!("Checking {}...", exercise).as_str());
+    let shared = shared_timing();
+    let checks = [
+        (
+            Exercise::check_property as fn(&Exercise) -> _,
+            Status::Counterexample,
+        ),
+        (Exercise::check_stability, Status::Unstable),
+    ];
+    for (check, status) in checks {
+        let start = Instant::now();
+        if let Some(Err(output)) = check(exercise) {
+            // Only a test which ran to its end failed because of the check itself
+            let failed_check = output.exceeded.is_none() && output.diagnostics.is_empty();
+            measurement_failed(result, start.elapsed(), output);
+            if failed_check {
+                result.status = status;
+            }
+            return;
+        }
+    }
 
-    let compilation = compile(exercise, &progress_bar)?;
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
+    drop(shared);
 
-    match result {
-        Ok(output) => {
-            if verbose {
-                println!("{}", output.stdout);
-            }
-            success!("Successfully tested {}", &exercise);
-            if let RunMode::Interactive = run_mode {
-                Ok(prompt_for_completion(&exercise, None))
-            } else {
-                Ok(true)
+    // No other job runs while the running time is measured
+    let start = Instant::now();
+    let measured = exercise.complexity.and_then(|_| {
+        let _exclusive = exclusive_timing();
+        exercise.measure_complexity()
+    });
+    match measured {
+        None => {}
+        Some(Ok(report)) => {
+            if report.exceeds() {
+                result.status = Status::TooSlow;
             }
+            result.complexity = Some(report);
         }
-        Err(output) => {
-            warn!(
-                "Testing of {} failed! Please try again. Here's the output:",
-                exercise
-            );
-            println!("{}", output.stdout);
-            Err(())
+        Some(Err(output)) => return measurement_failed(result, start.elapsed(), output),
+    }
+
+    let _shared = shared_timing();
+    let start = Instant::now();
+    match exercise.count_comparisons() {
+        None => {}
+        Some(Ok(report)) => {
+            if report.exceeds() && result.status == Status::Passed {
+                result.status = Status::TooManyComparisons;
+            }
+            result.comparisons = Some(report);
         }
+        Some(Err(output)) => measurement_failed(result, start.elapsed(), output),
     }
 }
 
-// Compile the given Exercise and return an object with information
-// about the state of the compilation
-fn compile<'a, 'b>(
-    exercise: &'a Exercise,
-    progress_bar: &'b ProgressBar,
-) -> Result<CompiledExercise<'a>, ()> {
-    let compilation_result = exercise.compile();
+fn measurement_failed(result: &mut ExerciseResult, duration: Duration, output: ExerciseOutput) {
+    result.status = match output.exceeded {
+        Some(_) => Status::LimitExceeded,
+        None => Status::Failed,
+    };
+    result.exceeded = output.exceeded;
+    result.error = output.error.clone();
+    result.execution = Some(Step::new(false, duration, output));
+}
 
-    match compilation_result {
-        Ok(compilation) => Ok(compilation),
-        Err(output) => {
-            progress_bar.finish_and_clear();
+// Report the result of the given Exercise to the end user.
+// On success, returns the output to show along the completion prompt, if any.
+// If the Exercise is a test, the output of the test harness is only
+// displayed if verbose is set to true
+pub fn report(
+    exercise: &Exercise,
+    result: &ExerciseResult,
+    verbose: bool,
+) -> Result<Option<String>, ()> {
+    let stdout = result.execution.as_ref().map_or("", |step| &step.stdout);
+    if !result.cases.is_empty() && (verbose || !result.passed()) {
+        print_cases(&result.cases);
+    }
+    if let Some(complexity) = &result.complexity {
+        print_complexity(complexity, verbose || !result.passed());
+    }
+    if let Some(comparisons) = &result.comparisons {
+        print_comparisons(comparisons);
+    }
+    match result.status {
+        Status::Cached => {
+            success!("{} is unchanged since it last passed", exercise);
+            Ok(None)
+        }
+        Status::CompileError => {
             warn!(
                 "Compiling of {} failed! Please try again. Here's the output:",
                 exercise
             );
-            println!("{}", output.stderr);
+            print_compilation_failure(result);
+            Err(())
+        }
+        Status::Failed => {
+            if exercise.mode == Mode::Test {
+                warn!(
+                    "Testing of {} failed! Please try again. Here's the output:",
+                    exercise
+                );
+                println!("{}", stdout);
+            } else {
+                warn!("Ran {} with errors", exercise);
+                println!("{}", stdout);
+            }
+            let stderr = result.execution.as_ref().map_or("", |step| &step.stderr);
+            if exercise.mode != Mode::Test || !stderr.is_empty() {
+                println!("{}", stderr);
+            }
+            Err(())
+        }
+        Status::LimitExceeded => {
+            if let Some(limit) = result.exceeded {
+                warn_limit_exceeded(exercise, limit);
+            }
+            println!("{}", stdout);
+            Err(())
+        }
+        Status::WrongOutput => {
+            warn!("{} didn't print the expected output", exercise);
+            print_output_mismatch(exercise, result);
+            Err(())
+        }
+        Status::TooSlow => {
+            warn!("{} is too slow for its declared complexity", exercise);
+            Err(())
+        }
+        Status::TooManyComparisons => {
+            warn!("{} makes too many comparisons", exercise);
             Err(())
         }
+        Status::Unstable => {
+            warn!("{} doesn't sort stably", exercise);
+            print_harness_failure(result);
+            println!("Records with equal keys have to stay in the order they were given in.");
+            Err(())
+        }
+        Status::Counterexample => {
+            warn!("{} fails on a generated input", exercise);
+            print_harness_failure(result);
+            println!("Set ALGO_SEED to another number to generate other inputs.");
+            Err(())
+        }
+        Status::Passed => match exercise.mode {
+            Mode::Test => {
+                if verbose {
+                    println!("{}", stdout);
+                }
+                success!("Successfully tested {}", exercise);
+                Ok(None)
+            }
+            Mode::Compile | Mode::Output => {
+                success!("Successfully ran {}!", exercise);
+                Ok(Some(stdout.to_string()))
+            }
+            Mode::Clippy => {
+                success!("Successfully compiled {}!", exercise);
+                Ok(None)
+            }
+        },
+    }
+}
+
+// Show the diagnostics of a failed compilation, or the raw
+// output of the compiler if it couldn't report any
+pub fn print_compilation_failure(result: &ExerciseResult) {
+    match &result.compilation {
+        Some(step) if !step.diagnostics.is_empty() => print_diagnostics(&step.diagnostics),
+        Some(step) => println!("{}", step.stderr),
+        None => {}
+    }
+}
+
+// Show the verdict of every case in a table, along with the first one which failed
+pub fn print_cases(cases: &[CaseResult]) {
+    let width = cases
+        .iter()
+        .map(|case| case.name.len())
+        .chain(Some("Case".len()))
+        .max()
+        .unwrap_or(0);
+    println!("{:width$}  Verdict  Time", "Case", width = width);
+    for case in cases {
+        let verdict = format!("{:7}", case.verdict);
+        let verdict = if case.verdict == Verdict::Accepted {
+            style(verdict).green()
+        } else {
+            style(verdict).red()
+        };
+        println!(
+            "{:width$}  {}  {:.2}s",
+            case.name,
+            verdict,
+            case.duration_secs,
+            width = width
+        );
+    }
+
+    let passed = cases
+        .iter()
+        .filter(|case| case.verdict == Verdict::Accepted)
+        .count();
+    println!();
+    println!("{} / {} cases passed.", passed, cases.len());
+    if let Some(case) = cases.iter().find(|case| case.verdict != Verdict::Accepted) {
+        println!("The output below is the one of case {}.", case.name);
+    }
+    println!();
+}
+
+// Show the complexity class the running time of an exercise grows like.
+// With details, the measurements are drawn as a chart
+pub fn print_complexity(report: &ComplexityReport, details: bool) {
+    if details {
+        println!("{}", chart(report));
+    }
+    println!(
+        "Its running time grows like O({}), the declared complexity is O({}).",
+        report.fitted, report.declared
+    );
+}
+
+// Show how many comparisons an exercise made on every kind of input
+pub fn print_comparisons(report: &ComparisonReport) {
+    let limit = report.bound.limit(report.n);
+    println!(
+        "Comparisons on {} numbers, at most {} for {}:",
+        report.n, limit, report.bound
+    );
+    for count in &report.counts {
+        let comparisons = format!("{:>10}", count.comparisons);
+        let comparisons = if count.comparisons > limit {
+            style(comparisons).red()
+        } else {
+            style(comparisons).green()
+        };
+        println!("  {:8}{}", count.input, comparisons);
+    }
+    println!();
+}
+
+// Show why the test of a harness failed, such as the counterexample it found
+pub fn print_harness_failure(result: &ExerciseResult) {
+    let stderr = result.execution.as_ref().map_or("", |step| &step.stderr);
+    match panic_message(stderr) {
+        Some(message) => println!("{}", message),
+        None => println!("{}", stderr),
     }
 }
 
-fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>) -> bool {
-    let context = match exercise.state() {
-        State::Done => return true,
-        State::Pending(context) => context,
+// Show how the output of the given Exercise differs from the expected output
+pub fn print_output_mismatch(exercise: &Exercise, result: &ExerciseResult) {
+    let expected = result.expected.as_deref().unwrap_or("");
+    let actual = result.execution.as_ref().map_or("", |step| &step.stdout);
+    let actual = exercise.whitespace.normalize(actual);
+    println!();
+    print_unified(&diff_lines(expected, &actual), "expected", "actual", 3);
+    println!();
+}
+
+// Tell the end user that the given Exercise was stopped because of a limit
+pub fn warn_limit_exceeded(exercise: &Exercise, limit: Limit) {
+    warn!("{} was stopped!", exercise);
+    match limit {
+        Limit::Time(_) => println!("It {}, look for a loop which never ends.", limit),
+        Limit::Memory(_) => println!(
+            "It {}, look for a collection which never stops growing.",
+            limit
+        ),
+    }
+}
+
+fn prompt_for_completion(
+    exercise: &Exercise,
+    prompt_output: Option<String>,
+    progress: &Progress,
+) -> bool {
+    let context = match exercise.state(progress) {
+        Ok(State::Done) => return true,
+        Ok(State::Pending(context)) => context,
+        Err(e) => {
+            warn!("{}", e);
+            return false;
+        }
     };
 
     let success_msg = match exercise.mode {
         Mode::Compile => "The code is compiling!",
+        Mode::Output => "The code is compiling, and prints the expected output!",
         Mode::Test => "The code is compiling, and the tests pass!",
         Mode::Clippy => "The code is compiling, and 📎 Clippy 📎 is happy!",
     };
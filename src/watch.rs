@@ -0,0 +1,570 @@
+use crate::diagnostic::{explain, Diagnostic};
+use crate::exercise::{Exercise, Section};
+use crate::hint::hint;
+use crate::list::{completion, list, Filter, Format};
+use crate::prerequisites::missing_prerequisites;
+use crate::progress::Progress;
+use crate::reset::reset;
+use crate::run::run;
+use crate::solution::solution;
+use crate::verify::verify;
+use console::style;
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The commands of the watch shell along with their description.
+// Any unambiguous prefix of a command name is accepted as well
+const COMMANDS: &[(&str, &str)] = &[
+    (
+        "hint [--all]",
+        "Reveals the next hint for the current exercise",
+    ),
+    (
+        "explain [code]",
+        "Explains the error code of the current exercise, or the given one",
+    ),
+    ("list", "Lists the exercises and whether they are done"),
+    ("next", "Moves on to the next pending exercise"),
+    ("skip", "Same as `next`"),
+    (
+        "run [name]",
+        "Runs the current exercise, or the one with the given name",
+    ),
+    (
+        "reset [--force]",
+        "Restores the current exercise to its original stub",
+    ),
+    (
+        "solution [--diff]",
+        "Shows the reference solution of the current exercise",
+    ),
+    ("progress", "Shows how many exercises are done"),
+    ("clear", "Clears the screen"),
+    ("help", "Shows the available commands"),
+    ("quit", "Stops watching and shows a summary of the session"),
+];
+
+// How watching the exercises came to an end
+pub enum WatchStatus {
+    // Every exercise is done
+    Finished,
+    // The end user quit the watch shell
+    Quit,
+}
+
+// How the watch session picks the exercise to work on
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Schedule {
+    // The first pending exercise, in the order of info.toml
+    Order,
+    // Any pending exercise whose prerequisites are done, in the order of info.toml
+    Prerequisites,
+}
+
+impl FromStr for Schedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Schedule, String> {
+        match s {
+            "order" => Ok(Schedule::Order),
+            "prerequisites" => Ok(Schedule::Prerequisites),
+            _ => Err(format!("unknown schedule: {}", s)),
+        }
+    }
+}
+
+// A command typed in the watch shell
+#[derive(PartialEq, Debug)]
+enum ShellCommand {
+    Hint { all: bool },
+    Explain(Option<String>),
+    List,
+    Next,
+    Run(Option<String>),
+    Reset { force: bool },
+    Solution { diff: bool },
+    Progress,
+    Clear,
+    Help,
+    Quit,
+}
+
+impl FromStr for ShellCommand {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<ShellCommand, String> {
+        let mut words = input.split_whitespace();
+        let name = resolve(words.next().unwrap_or_default())?;
+        let argument = words.next();
+        if let Some(extra) = words.next() {
+            return Err(format!("unexpected argument for {}: {}", name, extra));
+        }
+
+        let command = match (name, argument) {
+            ("hint", Some("--all")) => ShellCommand::Hint { all: true },
+            ("run", name) => ShellCommand::Run(name.map(String::from)),
+            ("explain", code) => ShellCommand::Explain(code.map(String::from)),
+            ("reset", Some("--force")) => ShellCommand::Reset { force: true },
+            ("solution", Some("--diff")) => ShellCommand::Solution { diff: true },
+            (_, Some(argument)) => {
+                return Err(format!("unexpected argument for {}: {}", name, argument))
+            }
+            ("hint", None) => ShellCommand::Hint { all: false },
+            ("list", None) => ShellCommand::List,
+            ("next", None) | ("skip", None) => ShellCommand::Next,
+            ("reset", None) => ShellCommand::Reset { force: false },
+            ("solution", None) => ShellCommand::Solution { diff: false },
+            ("progress", None) => ShellCommand::Progress,
+            ("clear", None) => ShellCommand::Clear,
+            ("help", None) => ShellCommand::Help,
+            ("quit", None) => ShellCommand::Quit,
+            _ => unreachable!("every command name is handled"),
+        };
+        Ok(command)
+    }
+}
+
+// Find the name of the command the given word stands for
+fn resolve(word: &str) -> Result<&'static str, String> {
+    let names: Vec<&'static str> = COMMANDS
+        .iter()
+        .map(|(usage, _)| usage.split(' ').next().unwrap())
+        .collect();
+    if let Some(name) = names.iter().find(|name| **name == word) {
+        return Ok(name);
+    }
+
+    let matching: Vec<&'static str> = names
+        .into_iter()
+        .filter(|name| name.starts_with(word))
+        .collect();
+    match matching.as_slice() {
+        [] => Err(format!("unknown command: {}", word)),
+        [name] => Ok(name),
+        _ => Err(format!(
+            "ambiguous command: {} could be {}",
+            word,
+            matching.join(", ")
+        )),
+    }
+}
+
+fn print_help() {
+    let width = COMMANDS.iter().map(|(usage, _)| usage.len()).max().unwrap();
+    println!("Available commands:");
+    for (usage, description) in COMMANDS {
+        println!("  {:width$}  {}", usage, description, width = width);
+    }
+    println!(
+        "Commands can be shortened as long as they stay unambiguous, e.g. `pro` for `progress`."
+    );
+}
+
+// The events the watch loop reacts to
+enum Event {
+    // A file changed below the watched directory
+    File(DebouncedEvent),
+    // A command was typed in the watch shell
+    Command(ShellCommand),
+}
+
+// The state shared by the watch loop and the commands of the watch shell
+struct Session<'a> {
+    exercises: &'a [Exercise],
+    sections: &'a [Section],
+    progress: &'a mut Progress,
+    verbose: bool,
+    schedule: Schedule,
+    // The index of the exercise the end user is working on
+    current: usize,
+    // The compiler diagnostics of the last failed verification
+    diagnostics: Vec<Diagnostic>,
+    // When the session started, along with what was done at that time
+    started: Instant,
+    done_before: HashSet<&'a str>,
+    attempts_before: u32,
+}
+
+impl<'a> Session<'a> {
+    fn new(
+        exercises: &'a [Exercise],
+        sections: &'a [Section],
+        progress: &'a mut Progress,
+        verbose: bool,
+        schedule: Schedule,
+    ) -> Session<'a> {
+        let done_before = exercises
+            .iter()
+            .filter(|e| e.is_done(progress))
+            .map(|e| e.name.as_str())
+            .collect();
+        let attempts_before = total_attempts(progress);
+        Session {
+            exercises,
+            sections,
+            progress,
+            verbose,
+            schedule,
+            current: 0,
+            diagnostics: Vec::new(),
+            started: Instant::now(),
+            done_before,
+            attempts_before,
+        }
+    }
+
+    fn current(&self) -> &'a Exercise {
+        &self.exercises[self.current]
+    }
+
+    fn is_done(&self, index: usize) -> bool {
+        self.exercises[index].is_done(self.progress)
+    }
+
+    // Whether the exercise at the given index can be worked on.
+    // Following the prerequisites, it has to wait for them to be done
+    fn is_available(&self, index: usize) -> bool {
+        self.schedule == Schedule::Order
+            || missing_prerequisites(&self.exercises[index], self.exercises, self.progress)
+                .is_empty()
+    }
+
+    // Verify the available exercises starting at the given index.
+    // Exercises which were skipped before, or which were waiting for their
+    // prerequisites, are verified once the following ones pass.
+    // Returns whether every exercise is done
+    fn verify_from(&mut self, mut start: usize) -> bool {
+        let exercises = self.exercises;
+        loop {
+            let available: Vec<Exercise> = (start..exercises.len())
+                .filter(|&i| self.is_available(i))
+                .map(|i| exercises[i].clone())
+                .collect();
+            let mut results = Vec::new();
+            match verify(&available, self.progress, &mut results, self.verbose, 1) {
+                // The result of the exercise which stopped the verification comes last
+                Err(_) => {
+                    let result = results.pop().unwrap();
+                    self.current = exercises
+                        .iter()
+                        .position(|e| e.name == result.name)
+                        .unwrap();
+                    self.diagnostics = result
+                        .compilation
+                        .map(|step| step.diagnostics)
+                        .unwrap_or_default();
+                    self.print_available();
+                    return false;
+                }
+                Ok(()) => {
+                    match (0..exercises.len()).find(|&i| !self.is_done(i) && self.is_available(i)) {
+                        Some(pending) => start = pending,
+                        None => return true,
+                    }
+                }
+            }
+        }
+    }
+
+    // Tell which other exercises can be worked on, following the prerequisites
+    fn print_available(&self) {
+        if self.schedule == Schedule::Order {
+            return;
+        }
+        let others: Vec<&str> = (0..self.exercises.len())
+            .filter(|&i| i != self.current && !self.is_done(i) && self.is_available(i))
+            .map(|i| self.exercises[i].name.as_str())
+            .collect();
+        if !others.is_empty() {
+            println!();
+            println!("You can also work on: {}", others.join(", "));
+        }
+    }
+
+    // React to a change of the file at the given path.
+    // Returns whether every exercise is done
+    fn file_changed(&mut self, path: &Path) -> bool {
+        let path = match path.canonicalize() {
+            Ok(path) => path,
+            Err(_) => return false,
+        };
+        match self.exercises.iter().position(|e| path.ends_with(&e.path)) {
+            Some(index) if !self.is_available(index) => {
+                let exercise = &self.exercises[index];
+                let missing: Vec<&str> =
+                    missing_prerequisites(exercise, self.exercises, self.progress)
+                        .into_iter()
+                        .map(|e| e.name.as_str())
+                        .collect();
+                println!(
+                    "{} requires {}, which should be done first.",
+                    exercise,
+                    missing.join(", ")
+                );
+                false
+            }
+            Some(index) => {
+                clear_screen();
+                self.verify_from(index)
+            }
+            None => false,
+        }
+    }
+
+    // Execute the given shell command, returning the status
+    // which ends the watch loop if there is one
+    fn execute(&mut self, command: ShellCommand) -> Option<WatchStatus> {
+        let exercises = self.exercises;
+        let current = self.current();
+        match command {
+            ShellCommand::Hint { all } => {
+                let _ = hint(current, self.progress, all);
+            }
+            ShellCommand::Explain(code) => {
+                let code = code.or_else(|| {
+                    self.diagnostics
+                        .iter()
+                        .find_map(|d| d.explainable_code())
+                        .map(String::from)
+                });
+                match code {
+                    Some(code) => {
+                        let _ = explain(&code);
+                    }
+                    None => println!("There is no compiler error to explain for {}.", current),
+                }
+            }
+            ShellCommand::List => {
+                let filter = Filter {
+                    solved: false,
+                    pending: false,
+                    mode: None,
+                    text: None,
+                };
+                list(
+                    exercises,
+                    self.sections,
+                    self.progress,
+                    &filter,
+                    Format::Table,
+                );
+            }
+            ShellCommand::Next => {
+                let next = (1..exercises.len())
+                    .map(|offset| (self.current + offset) % exercises.len())
+                    .find(|&i| !self.is_done(i) && self.is_available(i));
+                match next {
+                    Some(next) => {
+                        clear_screen();
+                        println!("Skipped {}, moving on to {}.", current, exercises[next]);
+                        println!();
+                        if self.verify_from(next) {
+                            return Some(WatchStatus::Finished);
+                        }
+                    }
+                    None => println!("There is no other pending exercise to move on to."),
+                }
+            }
+            ShellCommand::Run(name) => {
+                let exercise = match name {
+                    Some(name) => match exercises.iter().find(|e| e.name == name) {
+                        Some(exercise) => exercise,
+                        None => {
+                            println!("No exercise found for your given name!");
+                            return None;
+                        }
+                    },
+                    None => current,
+                };
+                let _ = run(exercise, self.progress, &mut Vec::new(), self.verbose);
+            }
+            ShellCommand::Reset { force } => {
+                let _ = reset(current, self.progress, force);
+            }
+            ShellCommand::Solution { diff } => {
+                let _ = solution(current, self.progress, false, diff);
+            }
+            ShellCommand::Progress => {
+                println!("{}", completion(self.done().count(), exercises.len()));
+                println!("You are working on {}.", current);
+            }
+            ShellCommand::Clear => println!("\x1B[2J\x1B[1;1H"),
+            ShellCommand::Help => print_help(),
+            ShellCommand::Quit => return Some(WatchStatus::Quit),
+        }
+        None
+    }
+
+    fn done(&self) -> impl Iterator<Item = &'a Exercise> + '_ {
+        self.exercises
+            .iter()
+            .filter(move |e| e.is_done(self.progress))
+    }
+
+    fn print_summary(&self) {
+        let elapsed = self.started.elapsed().as_secs();
+        let completed: Vec<&str> = self
+            .done()
+            .map(|e| e.name.as_str())
+            .filter(|name| !self.done_before.contains(name))
+            .collect();
+
+        println!();
+        println!("{}", style("Session summary").bold());
+        println!("Time spent: {}m {:02}s", elapsed / 60, elapsed % 60);
+        println!(
+            "Attempts: {}",
+            total_attempts(self.progress) - self.attempts_before
+        );
+        if completed.is_empty() {
+            println!("Completed exercises: none");
+        } else {
+            println!(
+                "Completed exercises: {} ({})",
+                completed.len(),
+                completed.join(", ")
+            );
+        }
+        println!("{}", completion(self.done().count(), self.exercises.len()));
+    }
+}
+
+fn total_attempts(progress: &Progress) -> u32 {
+    progress.exercises.values().map(|p| p.attempts).sum()
+}
+
+/* Clears the terminal with an ANSI escape code.
+Works in UNIX and newer Windows terminals. */
+fn clear_screen() {
+    println!("\x1Bc");
+}
+
+// Read the commands typed by the end user and pass them on to the watch loop.
+// The shell stops once the end user quits or the input is closed
+fn spawn_watch_shell(events: Sender<Event>) {
+    println!("Type 'help' to see the available commands, or 'hint' to get help with the current exercise");
+    thread::spawn(move || loop {
+        let mut input = String::new();
+        match io::stdin().read_line(&mut input) {
+            Ok(0) => break,
+            Ok(_) => {
+                let input = input.trim();
+                if input.is_empty() {
+                    continue;
+                }
+                match input.parse::<ShellCommand>() {
+                    Ok(command) => {
+                        let quit = command == ShellCommand::Quit;
+                        if events.send(Event::Command(command)).is_err() || quit {
+                            break;
+                        }
+                    }
+                    Err(error) => {
+                        println!("{}", error);
+                        println!("Type 'help' to see the available commands");
+                    }
+                }
+            }
+            Err(error) => println!("error reading command: {}", error),
+        }
+    });
+}
+
+// Pass the file events on to the watch loop until the watcher is dropped
+fn forward_file_events(files: Receiver<DebouncedEvent>, events: Sender<Event>) {
+    thread::spawn(move || {
+        for event in files {
+            if events.send(Event::File(event)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+// Verify the exercises, then verify them again whenever one of them is edited
+// below the given root, until every one of them is done or the end user
+// quits the watch shell
+pub fn watch(
+    exercises: &[Exercise],
+    sections: &[Section],
+    root: &Path,
+    progress: &mut Progress,
+    verbose: bool,
+    schedule: Schedule,
+) -> notify::Result<WatchStatus> {
+    let (tx, rx) = channel();
+    let (file_tx, file_rx) = channel();
+
+    let mut watcher: RecommendedWatcher = Watcher::new(file_tx, Duration::from_secs(2))?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+    forward_file_events(file_rx, tx.clone());
+
+    clear_screen();
+
+    let mut session = Session::new(exercises, sections, progress, verbose, schedule);
+    if session.verify_from(0) {
+        return Ok(WatchStatus::Finished);
+    }
+    spawn_watch_shell(tx.clone());
+    loop {
+        match rx.recv() {
+            Ok(Event::File(
+                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b),
+            )) if b.extension() == Some(OsStr::new("rs")) && b.exists() => {
+                if session.file_changed(&b) {
+                    return Ok(WatchStatus::Finished);
+                }
+            }
+            Ok(Event::File(_)) => {}
+            Ok(Event::Command(command)) => match session.execute(command) {
+                Some(WatchStatus::Quit) => {
+                    let _ = watcher.unwatch(root);
+                    drop(watcher);
+                    session.print_summary();
+                    return Ok(WatchStatus::Quit);
+                }
+                Some(WatchStatus::Finished) => return Ok(WatchStatus::Finished),
+                None => {}
+            },
+            Err(e) => println!("watch error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_shell_command() {
+        assert_eq!("hint".parse(), Ok(ShellCommand::Hint { all: false }));
+        assert_eq!("skip".parse(), Ok(ShellCommand::Next));
+        assert_eq!(
+            "run merge_sort".parse(),
+            Ok(ShellCommand::Run(Some("merge_sort".to_string())))
+        );
+        assert_eq!(
+            "reset --force".parse(),
+            Ok(ShellCommand::Reset { force: true })
+        );
+        assert_eq!("pro".parse(), Ok(ShellCommand::Progress));
+        assert!("list --all".parse::<ShellCommand>().is_err());
+        assert!("jump".parse::<ShellCommand>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_ambiguous_prefix() {
+        assert_eq!(resolve("q"), Ok("quit"));
+        assert_eq!(
+            resolve("s"),
+            Err("ambiguous command: s could be skip, solution".to_string())
+        );
+    }
+}
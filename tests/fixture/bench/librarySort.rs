@@ -0,0 +1,10 @@
+fn library_sort(array: &mut [i64]) {
+    array.sort_unstable();
+}
+
+#[test]
+fn test_library_sort() {
+    let mut array = [3, 1, 2];
+    library_sort(&mut array);
+    assert_eq!(array, [1, 2, 3]);
+}
@@ -0,0 +1,19 @@
+use std::io::Read;
+
+// Doubles the number read from the input, but mishandles some inputs
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    let n: i64 = input.trim().parse().unwrap();
+    if n == 0 {
+        loop {}
+    }
+    if n < 0 {
+        panic!("negative input");
+    }
+    if n == 3 {
+        println!("7");
+        return;
+    }
+    println!("{}", n * 2);
+}
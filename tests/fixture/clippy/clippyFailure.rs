@@ -0,0 +1,6 @@
+fn main() {
+    let done = true;
+    if done == true {
+        println!("Done!");
+    }
+}
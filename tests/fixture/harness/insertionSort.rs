@@ -0,0 +1,16 @@
+fn insertion_sort<T: Ord>(array: &mut [T]) {
+    for i in 1..array.len() {
+        let mut j = i;
+        while j > 0 && array[j - 1] > array[j] {
+            array.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+#[test]
+fn test_insertion_sort() {
+    let mut array = [3, 1, 2];
+    insertion_sort(&mut array);
+    assert_eq!(array, [1, 2, 3]);
+}
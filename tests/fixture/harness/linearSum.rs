@@ -0,0 +1,8 @@
+fn sum(array: &[i64]) -> i64 {
+    array.iter().sum()
+}
+
+#[test]
+fn test_sum() {
+    assert_eq!(sum(&[1, 2, 3]), 6);
+}
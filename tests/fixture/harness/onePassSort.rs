@@ -0,0 +1,14 @@
+fn one_pass_sort<T: Ord>(array: &mut [T]) {
+    for j in 1..array.len() {
+        if array[j - 1] > array[j] {
+            array.swap(j - 1, j);
+        }
+    }
+}
+
+#[test]
+fn test_one_pass_sort() {
+    let mut array = [3, 1, 2];
+    one_pass_sort(&mut array);
+    assert_eq!(array, [1, 2, 3]);
+}
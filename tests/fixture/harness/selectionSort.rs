@@ -0,0 +1,13 @@
+fn selection_sort<T: Ord>(array: &mut [T]) {
+    for i in 0..array.len() {
+        let smallest = (i..array.len()).min_by(|&a, &b| array[a].cmp(&array[b]));
+        array.swap(i, smallest.unwrap());
+    }
+}
+
+#[test]
+fn test_selection_sort() {
+    let mut array = [3, 1, 2];
+    selection_sort(&mut array);
+    assert_eq!(array, [1, 2, 3]);
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let huge = vec![1u8; 1 << 30];
+    println!("{}", huge[huge.len() / 2]);
+}
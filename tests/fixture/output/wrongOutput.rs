@@ -0,0 +1,5 @@
+fn main() {
+    println!("1");
+    println!("two");
+    println!("3");
+}
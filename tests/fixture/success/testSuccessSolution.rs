@@ -0,0 +1,4 @@
+#[test]
+fn passing() {
+    println!("THE REFERENCE SOLUTION");
+}
@@ -0,0 +1 @@
+pub const SAMPLE: [i32; 3] = [1, 2, 3];
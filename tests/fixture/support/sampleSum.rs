@@ -0,0 +1,14 @@
+fn sum(numbers: &[i32]) -> i32 {
+    numbers.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use support::SAMPLE;
+
+    #[test]
+    fn test_sum() {
+        assert_eq!(sum(&SAMPLE), 6);
+    }
+}
@@ -1,9 +1,67 @@
 use assert_cmd::prelude::*;
 use glob::glob;
 use predicates::boolean::PredicateBooleanExt;
-use std::fs::File;
-use std::io::Read;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// A copy of one of the fixtures in a directory of its own, removed once the
+// test is over, so that the progress a test records is never seen by another
+struct Fixture(PathBuf);
+
+impl Deref for Fixture {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for Fixture {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn fixture(name: &str) -> Fixture {
+    static COPIES: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "algo_fixture_{}_{}_{}",
+        name,
+        std::process::id(),
+        COPIES.fetch_add(1, Ordering::SeqCst)
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    copy_fixture(&Path::new("tests/fixture").join(name), &dir).unwrap();
+    Fixture(dir)
+}
+
+// Copy a fixture without what earlier runs left in it
+fn copy_fixture(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == "target" || name == ".algo" {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            copy_fixture(&entry.path(), &to.join(&name))?;
+        } else {
+            fs::copy(entry.path(), to.join(&name))?;
+        }
+    }
+    Ok(())
+}
 
 #[test]
 fn runs_without_arguments() {
@@ -20,72 +78,121 @@ fn fails_when_in_wrong_dir() {
         .code(1);
 }
 
+#[test]
+fn fails_with_position_of_config_error() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("list")
+        .current_dir("tests/fixture/config")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("info.toml:3:7: expected a value"));
+}
+
 #[test]
 fn verify_all_success() {
+    let dir = fixture("success");
     Command::cargo_bin("algo")
         .unwrap()
         .arg("v")
-        .current_dir("tests/fixture/success")
+        .current_dir(&dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn verify_all_success_in_parallel() {
+    let dir = fixture("success");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["v", "--jobs", "2"])
+        .current_dir(&dir)
         .assert()
         .success();
 }
 
+#[test]
+fn verify_skips_unchanged_exercises() {
+    let dir = fixture("cache");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("v")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Successfully tested"));
+
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("v")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("unchanged since it last passed"));
+}
+
 #[test]
 fn verify_fails_if_some_fails() {
+    let dir = fixture("failure");
     Command::cargo_bin("algo")
         .unwrap()
         .arg("v")
-        .current_dir("tests/fixture/failure")
+        .current_dir(&dir)
         .assert()
         .code(1);
 }
 
 #[test]
 fn run_single_compile_success() {
+    let dir = fixture("success");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["r", "compSuccess"])
-        .current_dir("tests/fixture/success/")
+        .args(["r", "compSuccess"])
+        .current_dir(&dir)
         .assert()
         .success();
 }
 
 #[test]
 fn run_single_compile_failure() {
+    let dir = fixture("failure");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["r", "compFailure"])
-        .current_dir("tests/fixture/failure/")
+        .args(["r", "compFailure"])
+        .current_dir(&dir)
         .assert()
         .code(1);
 }
 
 #[test]
 fn run_single_test_success() {
+    let dir = fixture("success");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["r", "testSuccess"])
-        .current_dir("tests/fixture/success/")
+        .args(["r", "testSuccess"])
+        .current_dir(&dir)
         .assert()
         .success();
 }
 
 #[test]
 fn run_single_test_failure() {
+    let dir = fixture("failure");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["r", "testFailure"])
-        .current_dir("tests/fixture/failure/")
+        .args(["r", "testFailure"])
+        .current_dir(&dir)
         .assert()
         .code(1);
 }
 
 #[test]
 fn run_single_test_not_passed() {
+    let dir = fixture("failure");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["r", "testNotPassed.rs"])
-        .current_dir("tests/fixture/failure/")
+        .args(["r", "testNotPassed.rs"])
+        .current_dir(&dir)
         .assert()
         .code(1);
 }
@@ -102,20 +209,22 @@ fn run_single_test_no_filename() {
 
 #[test]
 fn run_single_test_no_exercise() {
+    let dir = fixture("failure");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["r", "compNoExercise.rs"])
-        .current_dir("tests/fixture/failure")
+        .args(["r", "compNoExercise.rs"])
+        .current_dir(&dir)
         .assert()
         .code(1);
 }
 
 #[test]
 fn get_hint_for_single_test() {
+    let dir = fixture("failure");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["h", "testFailure"])
-        .current_dir("tests/fixture/failure")
+        .args(["h", "testFailure"])
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout("Hello!\n");
@@ -123,27 +232,36 @@ fn get_hint_for_single_test() {
 
 #[test]
 fn all_exercises_require_confirmation() {
-    for exercise in glob("rust-cs-fundamentals/**/*.rs").unwrap() {
+    for exercise in glob("fundamentals/**/*.rs").unwrap() {
         let path = exercise.unwrap();
+        if path.starts_with("fundamentals/support") {
+            continue;
+        }
         let source = {
             let mut file = File::open(&path).unwrap();
             let mut s = String::new();
             file.read_to_string(&mut s).unwrap();
             s
         };
-        source.matches("// I AM NOT DONE").next().expect(&format!(
-            "There should be an `I AM NOT DONE` annotation in {:?}",
-            path
-        ));
+        source
+            .matches("// I AM NOT DONE")
+            .next()
+            .unwrap_or_else(|| {
+                panic!(
+                    "There should be an `I AM NOT DONE` annotation in {:?}",
+                    path
+                )
+            });
     }
 }
 
 #[test]
 fn run_compile_exercise_does_not_prompt() {
+    let dir = fixture("state");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["r", "pending_exercise"])
-        .current_dir("tests/fixture/state")
+        .args(["r", "pending_exercise"])
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout(predicates::str::contains("I AM NOT DONE").not());
@@ -151,10 +269,11 @@ fn run_compile_exercise_does_not_prompt() {
 
 #[test]
 fn run_test_exercise_does_not_prompt() {
+    let dir = fixture("state");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["r", "pending_test_exercise"])
-        .current_dir("tests/fixture/state")
+        .args(["r", "pending_test_exercise"])
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout(predicates::str::contains("I AM NOT DONE").not());
@@ -162,10 +281,11 @@ fn run_test_exercise_does_not_prompt() {
 
 #[test]
 fn run_single_test_success_with_output() {
+    let dir = fixture("success");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["--nocapture", "r", "testSuccess"])
-        .current_dir("tests/fixture/success/")
+        .args(["--nocapture", "r", "testSuccess"])
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout(predicates::str::contains("THIS TEST TOO SHALL PAS"));
@@ -173,11 +293,782 @@ fn run_single_test_success_with_output() {
 
 #[test]
 fn run_single_test_success_without_output() {
+    let dir = fixture("success");
     Command::cargo_bin("algo")
         .unwrap()
-        .args(&["r", "testSuccess"])
-        .current_dir("tests/fixture/success/")
+        .args(["r", "testSuccess"])
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout(predicates::str::contains("THIS TEST TOO SHALL PAS").not());
 }
+
+#[test]
+fn run_records_progress() {
+    let dir = fixture("progress");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "progressExercise"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+
+    let progress = fs::read_to_string(dir.join(".algo/progress.toml")).unwrap();
+    assert!(progress.contains("[exercises.progressExercise]"));
+    assert!(progress.contains("solved = true"));
+    assert!(progress.contains("attempts = 1"));
+}
+
+#[test]
+fn list_filters_by_mode() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["l", "--mode", "test"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("testSuccess"))
+        .stdout(predicates::str::contains("compSuccess").not());
+}
+
+#[test]
+fn list_pending_as_json() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["l", "--pending", "--format", "json"])
+        .current_dir("tests/fixture/state/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(r#""name": "pending_exercise""#))
+        .stdout(predicates::str::contains(r#""total": 2"#));
+}
+
+#[test]
+fn list_section_under_its_title() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["l", "--section", "graphs"])
+        .current_dir("tests/fixture/sections/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Graph week"))
+        .stdout(predicates::str::contains("graphSuccess"))
+        .stdout(predicates::str::contains("sortSuccess").not());
+}
+
+#[test]
+fn list_metadata_as_json() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["l", "--tag", "arrays", "--format", "json"])
+        .current_dir("tests/fixture/sections/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(r#""section": "sorts""#))
+        .stdout(predicates::str::contains(r#""difficulty": "easy""#))
+        .stdout(predicates::str::contains(r#""estimated_minutes": 10"#))
+        .stdout(predicates::str::contains(r#""total": 1"#));
+}
+
+#[test]
+fn verify_only_section() {
+    let dir = fixture("sections");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["v", "--section", "sorts"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["v", "--section", "graphs"])
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn verify_only_tag() {
+    let dir = fixture("sections");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["v", "--tag", "graphs"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("graphFailure").not());
+}
+
+#[test]
+fn fails_with_unknown_section() {
+    let dir = fixture("sections");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["v", "--section", "trees"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("The sections are: sorts, graphs"));
+}
+
+#[test]
+fn list_tree_of_prerequisites() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["l", "--tree"])
+        .current_dir("tests/fixture/prerequisites/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "compFirst  Pending\n└── compLocked",
+        ))
+        .stdout(predicates::str::contains("\ncompUnrelated  Pending"));
+}
+
+#[test]
+fn tracks_lists_every_track() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("tracks")
+        .current_dir("tests/fixture/tracks/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("* default"))
+        .stdout(predicates::str::contains("Basics"))
+        .stdout(predicates::str::contains("  advanced"))
+        .stdout(predicates::str::contains("Advanced"));
+}
+
+#[test]
+fn fails_with_unknown_track() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["--track", "expert", "list"])
+        .current_dir("tests/fixture/tracks/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("No track is named `expert`."));
+}
+
+#[test]
+fn fails_with_section_of_track_without_sections() {
+    let dir = fixture("tracks");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["--track", "advanced", "list", "--section", "graphs"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "tracks/advanced.toml doesn't declare any section.",
+        ));
+}
+
+#[test]
+fn track_is_remembered_with_its_own_progress() {
+    let dir = fixture("tracks");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["--track", "advanced", "verify"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert!(dir.join(".algo/tracks/advanced/progress.toml").exists());
+    assert!(!dir.join(".algo/progress.toml").exists());
+
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("list")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("compAdvanced"))
+        .stdout(predicates::str::contains("compSuccess").not());
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("tracks")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("  default   0 / 1"))
+        .stdout(predicates::str::contains("* advanced  1 / 1"));
+
+    fs::write(
+        dir.join(".algo/tracks/advanced/progress.toml"),
+        "version = ",
+    )
+    .unwrap();
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("tracks")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "Could not read your progress on the advanced track",
+        ));
+}
+
+// Create a scratch directory holding a copy of a single exercise
+fn scratch_dir(test_name: &str, exercise_source: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("algo_{}_{}", test_name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("fundamentals/algorithms/sorts")).unwrap();
+    fs::write(
+        dir.join("info.toml"),
+        r#"[[exercises]]
+name = "heap_sort"
+path = "fundamentals/algorithms/sorts/heap_sort.rs"
+mode = "test"
+hint = ""
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("fundamentals/algorithms/sorts/heap_sort.rs"),
+        exercise_source,
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn list_shows_missing_exercise() {
+    let dir = scratch_dir("list_missing", "fn main() {}\n");
+    fs::remove_file(dir.join("fundamentals/algorithms/sorts/heap_sort.rs")).unwrap();
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("list")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Missing"))
+        .stdout(predicates::str::contains("You completed 0 / 1 exercises"));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn reset_requires_force_for_modified_exercise() {
+    let dir = scratch_dir("reset_force", "fn main() {}\n");
+    let exercise_path = dir.join("fundamentals/algorithms/sorts/heap_sort.rs");
+
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["reset", "heap_sort"])
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+    assert_eq!(
+        fs::read_to_string(&exercise_path).unwrap(),
+        "fn main() {}\n"
+    );
+
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["reset", "heap_sort", "--force"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let restored = fs::read_to_string(&exercise_path).unwrap();
+    assert!(restored.contains("// I AM NOT DONE"));
+    assert!(restored.contains("fn heap_sort"));
+
+    let backups: Vec<_> = fs::read_dir(dir.join(".algo/backups")).unwrap().collect();
+    assert_eq!(backups.len(), 1);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+// Put an exercise of the course back as it was once the test is over
+struct RestoreExercise {
+    path: PathBuf,
+    source: String,
+}
+
+impl Drop for RestoreExercise {
+    fn drop(&mut self) {
+        let _ = fs::write(&self.path, &self.source);
+    }
+}
+
+#[test]
+fn reset_requires_force_after_rebuilding_with_modified_exercise() {
+    let path = PathBuf::from("fundamentals/algorithms/sorts/heap_sort.rs");
+    let source = fs::read_to_string(&path).unwrap();
+    let modified = format!("{}\n// Solved by the learner\n", source);
+    let _restore = RestoreExercise {
+        path: path.clone(),
+        source,
+    };
+    fs::write(&path, &modified).unwrap();
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let built = Command::new(cargo)
+        .args(["build", "--bin", "algo"])
+        .status()
+        .unwrap();
+    assert!(built.success());
+
+    let dir = scratch_dir("reset_rebuilt", &modified);
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["reset", "heap_sort"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("has been modified"));
+    assert_eq!(fs::read_to_string(dir.join(&path)).unwrap(), modified);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn solution_requires_completion() {
+    let dir = fixture("failure");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["solution", "testFailure"])
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn solution_with_force() {
+    let dir = fixture("success");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["solution", "testSuccess", "--force"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("THE REFERENCE SOLUTION"));
+}
+
+#[test]
+fn check_content_succeeds() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("check-content")
+        .assert()
+        .success();
+}
+
+#[test]
+fn check_content_fails_on_drift() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("check-content")
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("has no `I AM NOT DONE` marker"));
+}
+
+#[test]
+fn run_stops_exercise_after_timeout() {
+    let dir = fixture("limits");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "infiniteLoop"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("timed out after 1 seconds"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn run_stops_exercise_exceeding_memory() {
+    let dir = fixture("limits");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "hugeAllocation"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "exceeded the memory limit of 64 MB",
+        ));
+}
+
+#[test]
+fn run_writes_junit_report() {
+    let dir = fixture("success");
+    let report = std::env::temp_dir().join(format!("algo_junit_{}.xml", std::process::id()));
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "testSuccess", "--report", "junit", "--output"])
+        .arg(&report)
+        .current_dir(&dir)
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&report).unwrap();
+    assert!(content.contains(r#"<testsuite name="testSuccess" tests="1" failures="0""#));
+    assert!(content.contains(r#"<testcase classname="testSuccess" name="passing"/>"#));
+    let _ = fs::remove_file(&report);
+}
+
+#[test]
+fn verify_writes_tap_report_on_failure() {
+    let dir = fixture("failure");
+    let report = std::env::temp_dir().join(format!("algo_tap_{}.tap", std::process::id()));
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["v", "--report", "tap", "--output"])
+        .arg(&report)
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+
+    let content = fs::read_to_string(&report).unwrap();
+    assert!(content.starts_with("TAP version 13\n1..1\nnot ok 1 - compFailure\n"));
+    let _ = fs::remove_file(&report);
+}
+
+#[test]
+fn watch_shell_quits_with_summary() {
+    let dir = scratch_dir("watch_quit", "// I AM NOT DONE\n#[test]\nfn passing() {}\n");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("watch")
+        .current_dir(&dir)
+        .with_stdin()
+        .buffer("progress\njump\nquit\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("You completed 0 / 1 exercises"))
+        .stdout(predicates::str::contains("unknown command: jump"))
+        .stdout(predicates::str::contains("Session summary"))
+        .stdout(predicates::str::contains("All exercises completed!").not());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn hint_levels_unlock_after_failures() {
+    let dir = scratch_dir("hint_levels", "// I AM NOT DONE\nfn main() { let }\n");
+    fs::write(
+        dir.join("info.toml"),
+        r#"[[exercises]]
+name = "heap_sort"
+path = "fundamentals/algorithms/sorts/heap_sort.rs"
+mode = "compile"
+hint = ["Nudge", "Approach", "Near solution"]
+unlock_hints_after = 1
+"#,
+    )
+    .unwrap();
+    let hint = |args: &[&str]| {
+        Command::cargo_bin("algo")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .assert()
+            .success()
+    };
+
+    hint(&["hint", "heap_sort"])
+        .stdout(predicates::str::contains("Hint 1 of 3:\nNudge"))
+        .stdout(predicates::str::contains(
+            "The next hint unlocks after 1 more failed attempts.",
+        ));
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["run", "heap_sort"])
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+    hint(&["hint", "heap_sort"])
+        .stdout(predicates::str::contains("Hint 2 of 3:\nApproach"))
+        .stdout(predicates::str::contains("Nudge").not());
+    hint(&["hint", "heap_sort", "--all"])
+        .stdout(predicates::str::contains("Nudge"))
+        .stdout(predicates::str::contains("Approach"))
+        .stdout(predicates::str::contains("Near solution").not());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn lsp_project_follows_exercise_list() {
+    let dir = scratch_dir("lsp", "// I AM NOT DONE\nfn main() {}\n");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("lsp")
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let project = fs::read_to_string(dir.join("rust-project.json")).unwrap();
+    assert!(project.contains(r#""root_module": "fundamentals/algorithms/sorts/heap_sort.rs""#));
+    assert!(!project.contains("merge_sort.rs"));
+
+    let mut info = fs::read_to_string(dir.join("info.toml")).unwrap();
+    info.push_str(
+        r#"
+[[exercises]]
+name = "merge_sort"
+path = "fundamentals/algorithms/sorts/merge_sort.rs"
+mode = "compile"
+hint = ""
+"#,
+    );
+    fs::write(dir.join("info.toml"), info).unwrap();
+    fs::write(
+        dir.join("fundamentals/algorithms/sorts/merge_sort.rs"),
+        "// I AM NOT DONE\nfn main() {}\n",
+    )
+    .unwrap();
+    Command::cargo_bin("algo")
+        .unwrap()
+        .arg("list")
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let project = fs::read_to_string(dir.join("rust-project.json")).unwrap();
+    assert!(project.contains(r#""root_module": "fundamentals/algorithms/sorts/merge_sort.rs""#));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn run_shows_condensed_diagnostics() {
+    let dir = fixture("failure");
+    let report = std::env::temp_dir().join(format!("algo_diag_{}.json", std::process::id()));
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "compFailure", "--report", "json", "--output"])
+        .arg(&report)
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "error: expected pattern, found `}`",
+        ))
+        .stdout(predicates::str::contains("--> compFailure.rs:3:1"))
+        .stdout(predicates::str::contains("aborting due to").not());
+
+    let content = fs::read_to_string(&report).unwrap();
+    assert!(content.contains(r#""diagnostics": ["#));
+    assert!(content.contains(r#""message": "expected pattern, found `}`""#));
+    let _ = fs::remove_file(&report);
+}
+
+#[test]
+fn explain_error_code() {
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["explain", "E0308"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Expected type did not match the received type.",
+        ));
+}
+
+#[test]
+fn run_output_with_stdin_file() {
+    let dir = fixture("output");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "sumLines"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_output_mismatch_shows_diff() {
+    let dir = fixture("output");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "wrongOutput"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("@@ -1,3 +1,3 @@"))
+        .stdout(predicates::str::contains("-2"))
+        .stdout(predicates::str::contains("+two"));
+}
+
+#[test]
+fn run_cases_reports_verdicts() {
+    let dir = fixture("cases");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "doubleCases"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::is_match(r"01\s+AC").unwrap())
+        .stdout(predicates::str::is_match(r"02\s+WA").unwrap())
+        .stdout(predicates::str::is_match(r"03\s+TLE").unwrap())
+        .stdout(predicates::str::is_match(r"04\s+RE").unwrap())
+        .stdout(predicates::str::contains("1 / 4 cases passed."));
+}
+
+#[test]
+fn run_complexity_within_declared_class() {
+    let dir = fixture("harness");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "linearSum"])
+        .current_dir(&dir)
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains(
+            "the declared complexity is O(n).",
+        ));
+}
+
+#[test]
+fn run_complexity_exceeding_declared_class() {
+    let dir = fixture("harness");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "selectionSort"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("expected for O(n log n)"))
+        .stdout(predicates::str::contains("grows like O(n^2)"))
+        .stdout(predicates::str::contains(
+            "too slow for its declared complexity",
+        ));
+}
+
+#[test]
+fn run_counts_comparisons() {
+    let dir = fixture("harness");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "insertionSort"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "Comparisons on 1024 numbers, at most 10240 for n log n:",
+        ))
+        .stdout(predicates::str::is_match(r"sorted\s+1023").unwrap())
+        .stdout(predicates::str::contains("makes too many comparisons"));
+}
+
+#[test]
+fn run_checks_stability() {
+    let dir = fixture("harness");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "selectionSortStability"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("doesn't sort stably"))
+        .stdout(predicates::str::contains(
+            "The sort is not stable: of the records with key",
+        ));
+}
+
+#[test]
+fn run_shrinks_generated_counterexample() {
+    let dir = fixture("harness");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "onePassSort"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("fails on a generated input"))
+        .stdout(predicates::str::contains("with the seed 20190417"))
+        .stdout(predicates::str::contains("input: [1, 1, 0]"))
+        .stdout(predicates::str::contains("actual: [1, 0, 1]"));
+}
+
+#[test]
+fn run_links_support_library() {
+    let dir = fixture("support");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "sampleSum"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert!(dir.join(".algo/support/libsupport.rlib").exists());
+}
+
+#[test]
+fn bench_compares_with_std_and_previous_run() {
+    let dir = fixture("bench");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["bench", "librarySort"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("sort_unstable"))
+        .stdout(predicates::str::contains("previous").not());
+
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["bench", "librarySort"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("previous"));
+
+    let progress = fs::read_to_string(dir.join(".algo/progress.toml")).unwrap();
+    assert_eq!(
+        progress
+            .matches("[[exercises.librarySort.benchmarks]]")
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn bench_requires_call() {
+    let dir = fixture("bench");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["bench", "noCall"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("has no call to benchmark"));
+}
+
+#[test]
+fn run_clippy_in_scratch_project() {
+    let dir = fixture("clippy");
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "clippyFailure"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("clippy::bool_comparison"));
+
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "clippySuccess"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+
+    // The scratch directory of every run is gone, nothing was compiled next to the exercises
+    assert!(!dir.join("target/algo").exists());
+    let leftovers = glob(&format!("{}/temp_*", dir.display())).unwrap().count();
+    assert_eq!(leftovers, 0);
+}
+
+#[test]
+fn run_compiles_in_cache_directory() {
+    let dir = fixture("success");
+    let cache = std::env::temp_dir().join(format!("algo_cache_{}", std::process::id()));
+    Command::cargo_bin("algo")
+        .unwrap()
+        .args(["r", "compSuccess"])
+        .env("XDG_CACHE_HOME", &cache)
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert!(cache.exists());
+    assert!(!cache.join("rust-cs-fundamentals").exists());
+    fs::remove_dir_all(cache).unwrap();
+}